@@ -0,0 +1,124 @@
+//! Compares the algorithms of `GF2Matrix` and `PackedGF2Matrix<T>` for every
+//! word type on the same random matrices.
+//!
+//! Matrices have as many columns as the word has bits, and either as many rows
+//! or one of the fixed numbers of rows in `TALL_ROWS`, so that every word type
+//! is also measured on the same large sizes. The systems solved have an
+//! invertible matrix, so that every right-hand side is consistent.
+//!
+//! The matrices come from the generators of the `rand` feature. Run with
+//! `cargo bench --features rand --bench gf2_matrix`, optionally followed by a
+//! filter such as `-- rank/PackedGF2Matrix<u64>`.
+
+use std::hint::black_box;
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use lin_algebra::matrix::{MatrixTrait, Number};
+use lin_algebra::packed_gf2_matrix::PackedGF2Matrix;
+use lin_algebra::GF2Matrix;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// Probabilities of an entry being one.
+const DENSITIES: [f64; 2] = [0.1, 0.5];
+
+/// Numbers of rows of the tall matrices, whatever the word type.
+const TALL_ROWS: [usize; 2] = [256, 1024];
+
+fn bench_word<T: Number>(c: &mut Criterion) {
+    let bits = 8 * std::mem::size_of::<T>();
+    let packed_name = format!("PackedGF2Matrix<u{bits}>");
+    // Seeded, so that runs are comparable.
+    let mut rng = ChaCha8Rng::seed_from_u64(0x9e37_79b9_7f4a_7c15);
+
+    for density in DENSITIES {
+        for rows in [bits].into_iter().chain(TALL_ROWS) {
+            let dense = GF2Matrix::random_sparse(rows, bits, density, &mut rng);
+            let packed = PackedGF2Matrix::<T>::from(&dense);
+            let shape = format!("{rows}x{bits}/d{density}");
+
+            let mut group = c.benchmark_group("echelon_form");
+            group.bench_with_input(BenchmarkId::new("GF2Matrix", &shape), &dense, |b, m| {
+                b.iter(|| m.echelon_form())
+            });
+            group.bench_with_input(BenchmarkId::new(&packed_name, &shape), &packed, |b, m| {
+                b.iter(|| m.echelon_form())
+            });
+            group.finish();
+
+            let mut group = c.benchmark_group("rank");
+            group.bench_with_input(BenchmarkId::new("GF2Matrix", &shape), &dense, |b, m| {
+                b.iter(|| m.rank())
+            });
+            group.bench_with_input(BenchmarkId::new(&packed_name, &shape), &packed, |b, m| {
+                b.iter(|| m.rank())
+            });
+            group.finish();
+
+            let mut group = c.benchmark_group("kernel");
+            group.bench_with_input(BenchmarkId::new("GF2Matrix", &shape), &dense, |b, m| {
+                b.iter(|| m.kernel())
+            });
+            group.bench_with_input(BenchmarkId::new(&packed_name, &shape), &packed, |b, m| {
+                b.iter(|| m.kernel())
+            });
+            group.finish();
+
+            let mut group = c.benchmark_group("image");
+            group.bench_with_input(BenchmarkId::new("GF2Matrix", &shape), &dense, |b, m| {
+                b.iter(|| m.image())
+            });
+            group.bench_with_input(BenchmarkId::new(&packed_name, &shape), &packed, |b, m| {
+                b.iter(|| m.image())
+            });
+            group.finish();
+        }
+    }
+
+    // The packed solvers hold a right-hand side column in one word, so the
+    // systems are square.
+    let dense = GF2Matrix::random_invertible(bits, &mut rng);
+    let packed = PackedGF2Matrix::<T>::from(&dense);
+    let shape = format!("{bits}x{bits}");
+
+    let b_dense: Vec<u8> = GF2Matrix::random(1, bits, &mut rng).elements.concat();
+    let b_packed = PackedGF2Matrix::<T>::from(&GF2Matrix::new(vec![b_dense.clone()])).row(0);
+
+    let mut group = c.benchmark_group("solve");
+    group.bench_with_input(BenchmarkId::new("GF2Matrix", &shape), &dense, |b, m| {
+        b.iter(|| m.solve(black_box(&b_dense)))
+    });
+    group.bench_with_input(BenchmarkId::new(&packed_name, &shape), &packed, |b, m| {
+        b.iter(|| m.solve(black_box(b_packed)))
+    });
+    group.finish();
+
+    let y_dense = GF2Matrix::random(bits, bits, &mut rng);
+    let y_packed = PackedGF2Matrix::<T>::from(&y_dense);
+
+    let mut group = c.benchmark_group("solve_matrix_system");
+    group.bench_with_input(BenchmarkId::new("GF2Matrix", &shape), &dense, |b, m| {
+        b.iter(|| m.solve_matrix_system(black_box(&y_dense)))
+    });
+    group.bench_with_input(BenchmarkId::new(&packed_name, &shape), &packed, |b, m| {
+        b.iter(|| m.solve_matrix_system(black_box(&y_packed)))
+    });
+    group.finish();
+}
+
+fn benches(c: &mut Criterion) {
+    bench_word::<u8>(c);
+    bench_word::<u16>(c);
+    bench_word::<u32>(c);
+    bench_word::<u64>(c);
+}
+
+criterion_group! {
+    name = gf2_matrix;
+    config = Criterion::default()
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(2));
+    targets = benches
+}
+criterion_main!(gf2_matrix);
@@ -0,0 +1,157 @@
+/* C bindings for the bit-packed GF(2) matrices of lin_algebra. */
+
+#ifndef LIN_ALGEBRA_H
+#define LIN_ALGEBRA_H
+
+/* Generated by cbindgen from capi/src/lib.rs: do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Maximum number of columns of a matrix.
+#define LA_MAX_COLUMNS 64
+
+// Result of a call to the C API.
+typedef enum LaStatus {
+  // The call succeeded.
+  LA_STATUS_OK = 0,
+  // A required pointer argument is null.
+  LA_STATUS_NULL_POINTER = 1,
+  // A dimension is out of range, or a row or vector has bits set beyond it.
+  LA_STATUS_INVALID_DIMENSIONS = 2,
+  // The matrix does not have full column rank.
+  LA_STATUS_NOT_FULL_RANK = 3,
+  // The linear system has no solution.
+  LA_STATUS_INCONSISTENT = 4,
+  // The output buffer is too small.
+  LA_STATUS_BUFFER_TOO_SMALL = 5,
+  // An internal error occurred.
+  LA_STATUS_INTERNAL = 6,
+} LaStatus;
+
+// Opaque handle to a bit-packed GF(2) matrix.
+//
+// Handles are created by [`la_matrix_new`] and by the functions returning a
+// new matrix, and must be released with [`la_matrix_free`].
+typedef struct LaMatrix LaMatrix;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates a matrix from its packed rows.
+//
+// # Arguments
+//
+// * `rows` - `nrows` packed rows; may be null if `nrows` is zero.
+// * `nrows` - Number of rows.
+// * `ncols` - Number of columns, between 1 and [`LA_MAX_COLUMNS`].
+// * `out` - Receives the new handle.
+//
+// # Returns
+//
+// [`LaStatus::InvalidDimensions`] if `ncols` is out of range or a row has bits
+// set beyond `ncols`.
+//
+// # Safety
+//
+// `rows` must point to `nrows` readable values unless `nrows` is zero, and
+// `out` must be null or valid for writes.
+enum LaStatus la_matrix_new(const uint64_t *rows,
+                            size_t nrows,
+                            size_t ncols,
+                            struct LaMatrix **out);
+
+// Releases a matrix handle. Passing null does nothing.
+//
+// # Safety
+//
+// `matrix` must be null or a handle returned by this library that has not
+// been released yet.
+void la_matrix_free(struct LaMatrix *matrix);
+
+// Writes the number of rows and columns of a matrix.
+//
+// # Safety
+//
+// `matrix` must be null or a live handle, and `nrows` and `ncols` must be
+// null or valid for writes.
+enum LaStatus la_matrix_shape(const struct LaMatrix *matrix, size_t *nrows, size_t *ncols);
+
+// Copies the packed rows of a matrix into a buffer.
+//
+// # Returns
+//
+// [`LaStatus::BufferTooSmall`] if `len` is smaller than the number of rows.
+//
+// # Safety
+//
+// `matrix` must be null or a live handle, and `rows` must be null or valid
+// for `len` writes.
+enum LaStatus la_matrix_rows(const struct LaMatrix *matrix, uint64_t *rows, size_t len);
+
+// Computes the reduced row echelon form of a matrix as a new handle.
+//
+// # Safety
+//
+// `matrix` must be null or a live handle, and `out` must be null or valid
+// for writes.
+enum LaStatus la_matrix_echelon_form(const struct LaMatrix *matrix, struct LaMatrix **out);
+
+// Computes the rank of a matrix.
+//
+// # Safety
+//
+// `matrix` must be null or a live handle, and `rank` must be null or valid
+// for writes.
+enum LaStatus la_matrix_rank(const struct LaMatrix *matrix, size_t *rank);
+
+// Computes a basis of the kernel of a matrix.
+//
+// The basis vectors are the rows of the new handle, which has as many
+// columns as `matrix` and one row per basis vector.
+//
+// # Safety
+//
+// `matrix` must be null or a live handle, and `out` must be null or valid
+// for writes.
+enum LaStatus la_matrix_kernel(const struct LaMatrix *matrix, struct LaMatrix **out);
+
+// Computes a basis of the image of a matrix, spanned by its rows.
+//
+// The basis vectors are the rows of the new handle, which has as many
+// columns as `matrix` and one row per basis vector.
+//
+// # Safety
+//
+// `matrix` must be null or a live handle, and `out` must be null or valid
+// for writes.
+enum LaStatus la_matrix_image(const struct LaMatrix *matrix, struct LaMatrix **out);
+
+// Solves `A * x = b`, where `A` is the matrix and `b` a packed vector of
+// `nrows` bits.
+//
+// # Returns
+//
+// - [`LaStatus::InvalidDimensions`] if the matrix has more than
+//   [`LA_MAX_COLUMNS`] rows or `b` has bits set beyond `nrows`.
+// - [`LaStatus::NotFullRank`] if the matrix does not have full column rank.
+// - [`LaStatus::Inconsistent`] if the system has no solution.
+//
+// # Safety
+//
+// `matrix` must be null or a live handle, and `x` must be null or valid for
+// writes.
+enum LaStatus la_matrix_solve(const struct LaMatrix *matrix, uint64_t b, uint64_t *x);
+
+// Returns a static, NUL-terminated description of a status.
+const char *la_status_message(enum LaStatus status);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* LIN_ALGEBRA_H */
@@ -0,0 +1,392 @@
+//! C ABI for the bit-packed GF(2) matrices of `lin_algebra`.
+//!
+//! Matrices are exposed as opaque [`LaMatrix`] handles wrapping a
+//! [`PackedGF2Matrix<u64>`]: each row is a `uint64_t` whose bit `ncols - 1 - j`
+//! holds column `j`, so matrices have at most [`LA_MAX_COLUMNS`] columns.
+//!
+//! Every fallible function returns a [`LaStatus`] and writes its result
+//! through an output pointer, which is left untouched unless the call
+//! succeeds. No function unwinds into C: unexpected panics are reported as
+//! [`LaStatus::Internal`].
+//!
+//! The C header `include/lin_algebra.h` is generated from this file by
+//! cbindgen.
+
+use std::ffi::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+
+use lin_algebra::packed_gf2_matrix::PackedGF2Matrix;
+
+/// Maximum number of columns of a matrix.
+pub const LA_MAX_COLUMNS: usize = 64;
+
+/// Result of a call to the C API.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer argument is null.
+    NullPointer = 1,
+    /// A dimension is out of range, or a row or vector has bits set beyond it.
+    InvalidDimensions = 2,
+    /// The matrix does not have full column rank.
+    NotFullRank = 3,
+    /// The linear system has no solution.
+    Inconsistent = 4,
+    /// The output buffer is too small.
+    BufferTooSmall = 5,
+    /// An internal error occurred.
+    Internal = 6,
+}
+
+/// Opaque handle to a bit-packed GF(2) matrix.
+///
+/// Handles are created by [`la_matrix_new`] and by the functions returning a
+/// new matrix, and must be released with [`la_matrix_free`].
+pub struct LaMatrix(PackedGF2Matrix<u64>);
+
+/// Runs `f`, reporting a panic as [`LaStatus::Internal`].
+fn guard(f: impl FnOnce() -> LaStatus) -> LaStatus {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(LaStatus::Internal)
+}
+
+/// Returns `true` if `value` has no bit set at position `bits` or above.
+fn fits(value: u64, bits: usize) -> bool {
+    bits >= u64::BITS as usize || value >> bits == 0
+}
+
+/// Stores `matrix` in a new handle written to `out`.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+unsafe fn write_matrix(out: *mut *mut LaMatrix, matrix: PackedGF2Matrix<u64>) -> LaStatus {
+    *out = Box::into_raw(Box::new(LaMatrix(matrix)));
+    LaStatus::Ok
+}
+
+/// Returns the packed rows of `matrix`.
+fn rows_of(matrix: &PackedGF2Matrix<u64>) -> Vec<u64> {
+    (0..matrix.nrows()).map(|row| matrix.row(row)).collect()
+}
+
+/// Creates a matrix from its packed rows.
+///
+/// # Arguments
+///
+/// * `rows` - `nrows` packed rows; may be null if `nrows` is zero.
+/// * `nrows` - Number of rows.
+/// * `ncols` - Number of columns, between 1 and [`LA_MAX_COLUMNS`].
+/// * `out` - Receives the new handle.
+///
+/// # Returns
+///
+/// [`LaStatus::InvalidDimensions`] if `ncols` is out of range or a row has bits
+/// set beyond `ncols`.
+///
+/// # Safety
+///
+/// `rows` must point to `nrows` readable values unless `nrows` is zero, and
+/// `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn la_matrix_new(
+    rows: *const u64,
+    nrows: usize,
+    ncols: usize,
+    out: *mut *mut LaMatrix,
+) -> LaStatus {
+    if out.is_null() || (rows.is_null() && nrows > 0) {
+        return LaStatus::NullPointer;
+    }
+    if ncols == 0 || ncols > LA_MAX_COLUMNS {
+        return LaStatus::InvalidDimensions;
+    }
+    let rows = if nrows == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(rows, nrows)
+    };
+    if !rows.iter().all(|&row| fits(row, ncols)) {
+        return LaStatus::InvalidDimensions;
+    }
+    guard(|| write_matrix(out, PackedGF2Matrix::new(rows.to_vec(), ncols)))
+}
+
+/// Releases a matrix handle. Passing null does nothing.
+///
+/// # Safety
+///
+/// `matrix` must be null or a handle returned by this library that has not
+/// been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn la_matrix_free(matrix: *mut LaMatrix) {
+    if !matrix.is_null() {
+        drop(Box::from_raw(matrix));
+    }
+}
+
+/// Writes the number of rows and columns of a matrix.
+///
+/// # Safety
+///
+/// `matrix` must be null or a live handle, and `nrows` and `ncols` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn la_matrix_shape(
+    matrix: *const LaMatrix,
+    nrows: *mut usize,
+    ncols: *mut usize,
+) -> LaStatus {
+    if matrix.is_null() || nrows.is_null() || ncols.is_null() {
+        return LaStatus::NullPointer;
+    }
+    let matrix = &(*matrix).0;
+    *nrows = matrix.nrows();
+    *ncols = matrix.ncols();
+    LaStatus::Ok
+}
+
+/// Copies the packed rows of a matrix into a buffer.
+///
+/// # Returns
+///
+/// [`LaStatus::BufferTooSmall`] if `len` is smaller than the number of rows.
+///
+/// # Safety
+///
+/// `matrix` must be null or a live handle, and `rows` must be null or valid
+/// for `len` writes.
+#[no_mangle]
+pub unsafe extern "C" fn la_matrix_rows(
+    matrix: *const LaMatrix,
+    rows: *mut u64,
+    len: usize,
+) -> LaStatus {
+    if matrix.is_null() || (rows.is_null() && len > 0) {
+        return LaStatus::NullPointer;
+    }
+    let matrix = &(*matrix).0;
+    if len < matrix.nrows() {
+        return LaStatus::BufferTooSmall;
+    }
+    for row in 0..matrix.nrows() {
+        *rows.add(row) = matrix.row(row);
+    }
+    LaStatus::Ok
+}
+
+/// Computes the reduced row echelon form of a matrix as a new handle.
+///
+/// # Safety
+///
+/// `matrix` must be null or a live handle, and `out` must be null or valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn la_matrix_echelon_form(
+    matrix: *const LaMatrix,
+    out: *mut *mut LaMatrix,
+) -> LaStatus {
+    if matrix.is_null() || out.is_null() {
+        return LaStatus::NullPointer;
+    }
+    let matrix = &(*matrix).0;
+    guard(|| write_matrix(out, matrix.echelon_form().0))
+}
+
+/// Computes the rank of a matrix.
+///
+/// # Safety
+///
+/// `matrix` must be null or a live handle, and `rank` must be null or valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn la_matrix_rank(matrix: *const LaMatrix, rank: *mut usize) -> LaStatus {
+    if matrix.is_null() || rank.is_null() {
+        return LaStatus::NullPointer;
+    }
+    let matrix = &(*matrix).0;
+    guard(|| {
+        *rank = matrix.rank();
+        LaStatus::Ok
+    })
+}
+
+/// Computes a basis of the kernel of a matrix.
+///
+/// The basis vectors are the rows of the new handle, which has as many
+/// columns as `matrix` and one row per basis vector.
+///
+/// # Safety
+///
+/// `matrix` must be null or a live handle, and `out` must be null or valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn la_matrix_kernel(
+    matrix: *const LaMatrix,
+    out: *mut *mut LaMatrix,
+) -> LaStatus {
+    if matrix.is_null() || out.is_null() {
+        return LaStatus::NullPointer;
+    }
+    let matrix = &(*matrix).0;
+    guard(|| write_matrix(out, PackedGF2Matrix::new(matrix.kernel(), matrix.ncols())))
+}
+
+/// Computes a basis of the image of a matrix, spanned by its rows.
+///
+/// The basis vectors are the rows of the new handle, which has as many
+/// columns as `matrix` and one row per basis vector.
+///
+/// # Safety
+///
+/// `matrix` must be null or a live handle, and `out` must be null or valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn la_matrix_image(
+    matrix: *const LaMatrix,
+    out: *mut *mut LaMatrix,
+) -> LaStatus {
+    if matrix.is_null() || out.is_null() {
+        return LaStatus::NullPointer;
+    }
+    let matrix = &(*matrix).0;
+    guard(|| write_matrix(out, PackedGF2Matrix::new(matrix.image(), matrix.ncols())))
+}
+
+/// Solves `A * x = b`, where `A` is the matrix and `b` a packed vector of
+/// `nrows` bits.
+///
+/// # Returns
+///
+/// - [`LaStatus::InvalidDimensions`] if the matrix has more than
+///   [`LA_MAX_COLUMNS`] rows or `b` has bits set beyond `nrows`.
+/// - [`LaStatus::NotFullRank`] if the matrix does not have full column rank.
+/// - [`LaStatus::Inconsistent`] if the system has no solution.
+///
+/// # Safety
+///
+/// `matrix` must be null or a live handle, and `x` must be null or valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn la_matrix_solve(matrix: *const LaMatrix, b: u64, x: *mut u64) -> LaStatus {
+    if matrix.is_null() || x.is_null() {
+        return LaStatus::NullPointer;
+    }
+    let matrix = &(*matrix).0;
+    let (nrows, ncols) = (matrix.nrows(), matrix.ncols());
+    if nrows > LA_MAX_COLUMNS || !fits(b, nrows) {
+        return LaStatus::InvalidDimensions;
+    }
+    guard(|| {
+        let rank = matrix.rank();
+        if rank < ncols {
+            return LaStatus::NotFullRank;
+        }
+        // With full column rank and at most 64 rows, 64 columns make the
+        // matrix square and invertible; otherwise `b` is appended as a last
+        // column, which raises the rank exactly when the system is
+        // inconsistent.
+        if ncols < LA_MAX_COLUMNS {
+            let augmented = rows_of(matrix)
+                .into_iter()
+                .enumerate()
+                .map(|(row, value)| (value << 1) | ((b >> (nrows - 1 - row)) & 1))
+                .collect();
+            if PackedGF2Matrix::new(augmented, ncols + 1).rank() > rank {
+                return LaStatus::Inconsistent;
+            }
+        }
+        *x = matrix.solve(b);
+        LaStatus::Ok
+    })
+}
+
+/// Returns a static, NUL-terminated description of a status.
+#[no_mangle]
+pub extern "C" fn la_status_message(status: LaStatus) -> *const c_char {
+    let message = match status {
+        LaStatus::Ok => c"success",
+        LaStatus::NullPointer => c"null pointer argument",
+        LaStatus::InvalidDimensions => c"invalid dimensions",
+        LaStatus::NotFullRank => c"matrix does not have full column rank",
+        LaStatus::Inconsistent => c"linear system is inconsistent",
+        LaStatus::BufferTooSmall => c"output buffer too small",
+        LaStatus::Internal => c"internal error",
+    };
+    message.as_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    fn new_matrix(rows: &[u64], ncols: usize) -> *mut LaMatrix {
+        let mut matrix = ptr::null_mut();
+        let status = unsafe { la_matrix_new(rows.as_ptr(), rows.len(), ncols, &mut matrix) };
+        assert_eq!(status, LaStatus::Ok);
+        matrix
+    }
+
+    #[test]
+    fn test_solve_reports_errors_instead_of_panicking() {
+        let mut x = 0;
+
+        let singular = new_matrix(&[0b11, 0b11], 2);
+        assert_eq!(
+            unsafe { la_matrix_solve(singular, 0b00, &mut x) },
+            LaStatus::NotFullRank
+        );
+
+        let tall = new_matrix(&[0b10, 0b01, 0b11], 2);
+        assert_eq!(
+            unsafe { la_matrix_solve(tall, 0b111, &mut x) },
+            LaStatus::Inconsistent
+        );
+        assert_eq!(
+            unsafe { la_matrix_solve(tall, 0b1000, &mut x) },
+            LaStatus::InvalidDimensions
+        );
+        assert_eq!(
+            unsafe { la_matrix_solve(tall, 0b110, &mut x) },
+            LaStatus::Ok
+        );
+        assert_eq!(x, 0b11);
+
+        let rows: Vec<u64> = (0..64).map(|row| 1u64 << row).collect();
+        let identity = new_matrix(&rows, 64);
+        assert_eq!(
+            unsafe { la_matrix_solve(identity, u64::MAX, &mut x) },
+            LaStatus::Ok
+        );
+        assert_eq!(x, u64::MAX);
+
+        unsafe {
+            la_matrix_free(singular);
+            la_matrix_free(tall);
+            la_matrix_free(identity);
+        }
+    }
+
+    #[test]
+    fn test_new_validates_arguments() {
+        let mut matrix = ptr::null_mut();
+        unsafe {
+            assert_eq!(
+                la_matrix_new(ptr::null(), 1, 4, &mut matrix),
+                LaStatus::NullPointer
+            );
+            assert_eq!(
+                la_matrix_new([0b1u64].as_ptr(), 1, 65, &mut matrix),
+                LaStatus::InvalidDimensions
+            );
+            assert_eq!(
+                la_matrix_new([0b10000u64].as_ptr(), 1, 4, &mut matrix),
+                LaStatus::InvalidDimensions
+            );
+        }
+        assert!(matrix.is_null());
+    }
+}
@@ -0,0 +1,129 @@
+/* Exercises the C API end to end; exits with a nonzero status on failure. */
+
+#include <stdio.h>
+
+#include "lin_algebra.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                   \
+    do {                                                                   \
+        if (!(condition)) {                                                \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,         \
+                    __LINE__, #condition);                                 \
+            failures++;                                                    \
+        }                                                                  \
+    } while (0)
+
+#define CHECK_STATUS(call, expected)                                       \
+    do {                                                                   \
+        LaStatus status_ = (call);                                         \
+        if (status_ != (expected)) {                                       \
+            fprintf(stderr, "%s:%d: %s returned \"%s\"\n", __FILE__,       \
+                    __LINE__, #call, la_status_message(status_));          \
+            failures++;                                                    \
+        }                                                                  \
+    } while (0)
+
+/* Returns 1 if the product of the packed rows by the vector x is b. */
+static int product_is(const uint64_t *rows, size_t nrows, uint64_t x,
+                      uint64_t b) {
+    for (size_t row = 0; row < nrows; row++) {
+        uint64_t bit = 0;
+        for (uint64_t word = rows[row] & x; word != 0; word &= word - 1) {
+            bit ^= 1;
+        }
+        if (bit != ((b >> (nrows - 1 - row)) & 1)) {
+            return 0;
+        }
+    }
+    return 1;
+}
+
+static void test_echelon_rank_kernel_image(void) {
+    /* Rows 110, 011, 101: the third row is the sum of the first two. */
+    const uint64_t rows[] = {0x6, 0x3, 0x5};
+    LaMatrix *m = NULL;
+    CHECK_STATUS(la_matrix_new(rows, 3, 3, &m), LA_STATUS_OK);
+
+    size_t nrows = 0, ncols = 0, rank = 0;
+    CHECK_STATUS(la_matrix_shape(m, &nrows, &ncols), LA_STATUS_OK);
+    CHECK(nrows == 3 && ncols == 3);
+    CHECK_STATUS(la_matrix_rank(m, &rank), LA_STATUS_OK);
+    CHECK(rank == 2);
+
+    LaMatrix *echelon = NULL;
+    uint64_t reduced[3];
+    CHECK_STATUS(la_matrix_echelon_form(m, &echelon), LA_STATUS_OK);
+    CHECK_STATUS(la_matrix_rows(echelon, reduced, 3), LA_STATUS_OK);
+    CHECK(reduced[0] == 0x5 && reduced[1] == 0x3 && reduced[2] == 0x0);
+    CHECK_STATUS(la_matrix_rows(echelon, reduced, 2), LA_STATUS_BUFFER_TOO_SMALL);
+
+    LaMatrix *kernel = NULL;
+    uint64_t kernel_rows[3];
+    CHECK_STATUS(la_matrix_kernel(m, &kernel), LA_STATUS_OK);
+    CHECK_STATUS(la_matrix_shape(kernel, &nrows, &ncols), LA_STATUS_OK);
+    CHECK(nrows == 1 && ncols == 3);
+    CHECK_STATUS(la_matrix_rows(kernel, kernel_rows, 3), LA_STATUS_OK);
+    CHECK(kernel_rows[0] == 0x7);
+    CHECK(product_is(rows, 3, kernel_rows[0], 0));
+
+    LaMatrix *image = NULL;
+    CHECK_STATUS(la_matrix_image(m, &image), LA_STATUS_OK);
+    CHECK_STATUS(la_matrix_shape(image, &nrows, &ncols), LA_STATUS_OK);
+    CHECK(nrows == 2 && ncols == 3);
+
+    la_matrix_free(image);
+    la_matrix_free(kernel);
+    la_matrix_free(echelon);
+    la_matrix_free(m);
+}
+
+static void test_solve(void) {
+    /* A 4x3 matrix of full column rank. */
+    const uint64_t rows[] = {0x4, 0x2, 0x1, 0x7};
+    LaMatrix *m = NULL;
+    uint64_t x = 0;
+    CHECK_STATUS(la_matrix_new(rows, 4, 3, &m), LA_STATUS_OK);
+
+    CHECK_STATUS(la_matrix_solve(m, 0xA, &x), LA_STATUS_OK);
+    CHECK(x == 0x5);
+    CHECK(product_is(rows, 4, x, 0xA));
+    CHECK_STATUS(la_matrix_solve(m, 0xB, &x), LA_STATUS_INCONSISTENT);
+    CHECK_STATUS(la_matrix_solve(m, 0x1B, &x), LA_STATUS_INVALID_DIMENSIONS);
+
+    const uint64_t singular_rows[] = {0x3, 0x3};
+    LaMatrix *singular = NULL;
+    CHECK_STATUS(la_matrix_new(singular_rows, 2, 2, &singular), LA_STATUS_OK);
+    CHECK_STATUS(la_matrix_solve(singular, 0x0, &x), LA_STATUS_NOT_FULL_RANK);
+
+    la_matrix_free(singular);
+    la_matrix_free(m);
+}
+
+static void test_invalid_arguments(void) {
+    const uint64_t rows[] = {0x10};
+    LaMatrix *m = NULL;
+    size_t rank = 0;
+
+    CHECK_STATUS(la_matrix_new(rows, 1, 4, &m), LA_STATUS_INVALID_DIMENSIONS);
+    CHECK_STATUS(la_matrix_new(rows, 1, 0, &m), LA_STATUS_INVALID_DIMENSIONS);
+    CHECK_STATUS(la_matrix_new(rows, 1, LA_MAX_COLUMNS + 1, &m),
+                 LA_STATUS_INVALID_DIMENSIONS);
+    CHECK_STATUS(la_matrix_new(NULL, 1, 8, &m), LA_STATUS_NULL_POINTER);
+    CHECK(m == NULL);
+    CHECK_STATUS(la_matrix_rank(NULL, &rank), LA_STATUS_NULL_POINTER);
+    la_matrix_free(NULL);
+}
+
+int main(void) {
+    test_echelon_rank_kernel_image();
+    test_solve();
+    test_invalid_arguments();
+
+    if (failures != 0) {
+        fprintf(stderr, "%d check(s) failed\n", failures);
+        return 1;
+    }
+    return 0;
+}
@@ -0,0 +1,67 @@
+//! Compiles `tests/c/test_capi.c` against the shipped header and the built
+//! library, and runs it.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const MANIFEST_DIR: &str = env!("CARGO_MANIFEST_DIR");
+
+/// Returns the directory holding the library artifacts of this build.
+fn library_dir() -> PathBuf {
+    // Integration tests run from `target/<profile>/deps`, next to the
+    // dynamic library of the crate.
+    env::current_exe()
+        .expect("cannot locate the test executable")
+        .parent()
+        .expect("test executable has no parent directory")
+        .to_path_buf()
+}
+
+#[test]
+fn test_shipped_header_is_up_to_date() {
+    let generated = Path::new(env!("OUT_DIR")).join("lin_algebra.h");
+    let shipped = Path::new(MANIFEST_DIR).join("include/lin_algebra.h");
+
+    assert_eq!(
+        fs::read_to_string(&shipped).expect("cannot read the shipped header"),
+        fs::read_to_string(&generated).expect("cannot read the generated header"),
+        "{} is stale: copy {} over it",
+        shipped.display(),
+        generated.display()
+    );
+}
+
+#[test]
+fn test_c_program() {
+    let library_dir = library_dir();
+    let executable = library_dir.join("test_capi");
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+
+    let status = Command::new(&compiler)
+        .arg(Path::new(MANIFEST_DIR).join("tests/c/test_capi.c"))
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(Path::new(MANIFEST_DIR).join("include"))
+        .arg("-L")
+        .arg(&library_dir)
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .arg("-llin_algebra_capi")
+        .arg("-o")
+        .arg(&executable)
+        .status()
+        .unwrap_or_else(|error| panic!("cannot run the C compiler `{compiler}`: {error}"));
+    assert!(status.success(), "compiling the C test program failed");
+
+    let output = Command::new(&executable)
+        .output()
+        .expect("cannot run the C test program");
+    assert!(
+        output.status.success(),
+        "C test program failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,274 @@
+use alloc::vec::Vec;
+
+use crate::gf2_vector::Gf2Vector;
+use crate::matrix::{MatrixCommon, Number};
+use crate::packed_gf2_matrix::PackedGF2Matrix;
+use crate::span::SpanIter;
+use crate::subspace::{echelon_basis, pivot, Subspace};
+use crate::GF2Matrix;
+
+/// Returns a solution of the system whose rows are `rows`, with `ncols`
+/// unknowns and right-hand side `b`, or `None` if it is inconsistent.
+///
+/// The augmented matrix `[A | b]` is brought to reduced row echelon form, the
+/// free unknowns are set to zero and each pivot unknown is read from the last
+/// entry of its row. Unlike [`GF2Matrix::solve`], `A` need not have full rank.
+fn particular_solution(rows: &[Gf2Vector], b: &Gf2Vector, ncols: usize) -> Option<Gf2Vector> {
+    let augmented = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| row.concat(&Gf2Vector::from_bits(&[b.get(i)])));
+
+    let mut x = Gf2Vector::zeros(ncols);
+    for row in echelon_basis(augmented) {
+        let p = pivot(&row);
+        if p == ncols {
+            return None;
+        }
+        x.set(p, row.get(ncols));
+    }
+    Some(x)
+}
+
+/// An affine subspace `offset + V` of `GF(2)^n`, where `V` is a [`Subspace`].
+///
+/// The offset is kept reduced modulo `V`, so that two affine subspaces are equal
+/// exactly when they are the same set. The solution set of a linear system, as
+/// returned by [`GF2Matrix::solution_set`], is an affine subspace whose
+/// direction is the kernel of the matrix.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::gf2_vector::Gf2Vector;
+/// # use lin_algebra::GF2Matrix;
+/// let a = GF2Matrix::new(vec![vec![1, 1, 0], vec![0, 1, 1]]);
+/// let solutions = a.solution_set(&Gf2Vector::from_bits(&[1, 0])).unwrap();
+///
+/// assert_eq!(solutions.dim(), 1);
+/// assert!(solutions.contains(&Gf2Vector::from_bits(&[1, 0, 0])));
+/// assert_eq!(solutions.elements::<u8>().collect::<Vec<_>>(), vec![0b011, 0b100]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AffineSubspace {
+    offset: Gf2Vector,
+    direction: Subspace,
+}
+
+impl AffineSubspace {
+    /// Creates the affine subspace `offset + direction`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` does not have length `direction.ambient_dim()`.
+    pub fn new(offset: Gf2Vector, direction: Subspace) -> Self {
+        Self {
+            offset: direction.coset_representative(&offset),
+            direction,
+        }
+    }
+
+    /// Returns the affine subspace made of the single point `v`.
+    pub fn point(v: Gf2Vector) -> Self {
+        let n = v.len();
+        Self::new(v, Subspace::zero(n))
+    }
+
+    /// Returns the offset, reduced modulo the direction.
+    pub fn offset(&self) -> &Gf2Vector {
+        &self.offset
+    }
+
+    /// Returns the direction, the subspace `V` of `offset + V`.
+    pub fn direction(&self) -> &Subspace {
+        &self.direction
+    }
+
+    /// Returns the dimension `n` of the ambient space `GF(2)^n`.
+    pub fn ambient_dim(&self) -> usize {
+        self.direction.ambient_dim()
+    }
+
+    /// Returns the dimension of the affine subspace, that of its direction.
+    pub fn dim(&self) -> usize {
+        self.direction.dim()
+    }
+
+    /// Returns `true` if `v` belongs to the affine subspace.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` does not have length [`Self::ambient_dim`].
+    pub fn contains(&self, v: &Gf2Vector) -> bool {
+        self.direction.contains(&(v ^ &self.offset))
+    }
+
+    /// Returns the intersection with `other`, or `None` if it is empty.
+    ///
+    /// A common point `o1 + u = o2 + w` is found by solving `u + w = o1 + o2`
+    /// for the coordinates of `u` and `w` in the bases of both directions. The
+    /// direction of the intersection is the intersection of the directions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ambient spaces differ.
+    pub fn intersection(&self, other: &AffineSubspace) -> Option<AffineSubspace> {
+        let n = self.ambient_dim();
+        assert_eq!(
+            n,
+            other.ambient_dim(),
+            "affine subspaces must lie in the same ambient space"
+        );
+        let (u, w) = (self.direction.basis(), other.direction.basis());
+
+        // Row `j` holds entry `j` of every basis vector of both directions.
+        let rows: Vec<Gf2Vector> = (0..n)
+            .map(|j| {
+                let bits: Vec<u8> = u.iter().chain(w).map(|v| v.get(j)).collect();
+                Gf2Vector::from_bits(&bits)
+            })
+            .collect();
+        let coordinates =
+            particular_solution(&rows, &(&self.offset ^ &other.offset), u.len() + w.len())?;
+
+        let mut point = self.offset.clone();
+        for i in coordinates.ones().take_while(|&i| i < u.len()) {
+            point ^= &u[i];
+        }
+        Some(AffineSubspace::new(
+            point,
+            self.direction.intersection(&other.direction),
+        ))
+    }
+
+    /// Iterates over the `2^dim` elements in the Gray-code order of
+    /// [`SpanIter`], packed as the rows of a [`PackedGF2Matrix<T>`], so that
+    /// each step is a single XOR.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ambient dimension is larger than the width of `T`, or if
+    /// the number of elements does not fit in a `usize`.
+    pub fn elements<T: Number>(&self) -> AffineElements<T> {
+        let basis: Vec<T> = self
+            .direction
+            .basis()
+            .iter()
+            .map(Gf2Vector::to_packed)
+            .collect();
+        SpanIter::with_offset(basis, self.offset.to_packed())
+    }
+}
+
+/// Iterator over the elements of an [`AffineSubspace`], returned by
+/// [`AffineSubspace::elements`].
+pub type AffineElements<T> = SpanIter<T, Vec<T>>;
+
+impl GF2Matrix {
+    /// Returns the set of solutions of `self * x = b`, or `None` if the system
+    /// is inconsistent.
+    ///
+    /// Unlike [`GF2Matrix::solve`], the matrix need not have full rank: the
+    /// solutions are a particular solution plus the kernel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b.len() != self.nrows()`.
+    pub fn solution_set(&self, b: &Gf2Vector) -> Option<AffineSubspace> {
+        assert_eq!(
+            b.len(),
+            self.nrows(),
+            "right-hand side length must equal the number of rows"
+        );
+        let rows: Vec<Gf2Vector> = self
+            .elements
+            .iter()
+            .map(|row| Gf2Vector::from_bits(row))
+            .collect();
+        let x = particular_solution(&rows, b, self.ncols())?;
+        Some(AffineSubspace::new(x, self.kernel_subspace()))
+    }
+}
+
+impl<T: Number> PackedGF2Matrix<T> {
+    /// Returns the set of solutions of `self * x = b`, or `None` if the system
+    /// is inconsistent.
+    ///
+    /// Unlike [`PackedGF2Matrix::solve`], the matrix need not have full rank
+    /// and may have more rows than `T` has bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b.len() != self.nrows()`.
+    pub fn solution_set(&self, b: &Gf2Vector) -> Option<AffineSubspace> {
+        assert_eq!(
+            b.len(),
+            self.nrows(),
+            "right-hand side length must equal the number of rows"
+        );
+        let rows: Vec<Gf2Vector> = (0..self.nrows())
+            .map(|i| Gf2Vector::from_packed(self.row(i), self.ncols()))
+            .collect();
+        let x = particular_solution(&rows, b, self.ncols())?;
+        Some(AffineSubspace::new(x, self.kernel_subspace()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solution_sets_match_brute_force() {
+        let a = PackedGF2Matrix::new(vec![0b110101u8, 0b011100, 0b101001, 0b111111], 6);
+        for rhs in 0u8..16 {
+            let b = Gf2Vector::from_packed(rhs, 4);
+            let expected: Vec<u8> = (0u8..64)
+                .filter(|x| a.mul_vector(&Gf2Vector::from_packed(*x, 6)) == b)
+                .collect();
+
+            let solutions = a.solution_set(&b);
+            assert_eq!(solutions, GF2Matrix::from(&a).solution_set(&b));
+            let Some(solutions) = solutions else {
+                assert!(expected.is_empty());
+                continue;
+            };
+            let mut elements: Vec<u8> = solutions.elements().collect();
+            assert_eq!(elements.len(), 1 << solutions.dim());
+            elements.sort();
+            assert_eq!(elements, expected);
+            assert!(expected
+                .iter()
+                .all(|&x| solutions.contains(&Gf2Vector::from_packed(x, 6))));
+        }
+    }
+
+    #[test]
+    fn test_intersection() {
+        let members = |s: &AffineSubspace| -> Vec<u8> {
+            let mut elements: Vec<u8> = s.elements().collect();
+            elements.sort();
+            elements
+        };
+        let plane = |offset: u8, u: u8, w: u8| {
+            AffineSubspace::new(
+                Gf2Vector::from_packed(offset, 5),
+                Subspace::from_packed(5, &[u, w]),
+            )
+        };
+
+        let s = plane(0b00001, 0b11000, 0b00110);
+        let t = plane(0b01011, 0b00110, 0b10100);
+        let common: Vec<u8> = members(&s)
+            .into_iter()
+            .filter(|x| members(&t).contains(x))
+            .collect();
+        assert_eq!(common, vec![0b11001, 0b11111]);
+        assert_eq!(members(&s.intersection(&t).unwrap()), common);
+
+        // Parallel planes do not meet.
+        let parallel = plane(0b00000, 0b11000, 0b00110);
+        assert_eq!(s.intersection(&parallel), None);
+        assert_eq!(s.intersection(&s), Some(s.clone()));
+    }
+}
@@ -0,0 +1,301 @@
+//! Random matrices for property-based testing.
+//!
+//! With the `proptest` feature, [`GF2Matrix`] and [`PackedGF2Matrix`]
+//! implement `proptest::arbitrary::Arbitrary`, and [`gf2_matrix`] and
+//! [`packed_gf2_matrix`] build strategies for given dimensions. With the
+//! `quickcheck` feature, both types implement `quickcheck::Arbitrary`.
+
+use core::ops::RangeInclusive;
+
+use crate::matrix::Number;
+use crate::packed_gf2_matrix::PackedGF2Matrix;
+use crate::GF2Matrix;
+
+/// Ranges of the numbers of rows and columns of generated matrices.
+///
+/// The number of columns of packed matrices is further limited to the width of
+/// the word type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatrixDimensions {
+    pub rows: RangeInclusive<usize>,
+    pub cols: RangeInclusive<usize>,
+}
+
+impl Default for MatrixDimensions {
+    fn default() -> Self {
+        Self {
+            rows: 1..=16,
+            cols: 1..=16,
+        }
+    }
+}
+
+/// Packs `bits`, given first column first, into a word.
+fn pack<T: Number>(bits: &[bool]) -> T {
+    bits.iter().fold(T::zero(), |acc, &bit| {
+        (acc << 1) ^ if bit { T::one() } else { T::zero() }
+    })
+}
+
+/// Clamps a range of numbers of columns to the width of `T`.
+fn packed_cols<T: Number>(cols: &RangeInclusive<usize>) -> RangeInclusive<usize> {
+    let width = core::mem::size_of::<T>() * 8;
+    (*cols.start()).min(width)..=(*cols.end()).min(width)
+}
+
+#[cfg(feature = "proptest")]
+pub use self::proptest_impls::{gf2_matrix, packed_gf2_matrix};
+
+#[cfg(feature = "proptest")]
+mod proptest_impls {
+    use core::fmt::Debug;
+
+    use proptest::arbitrary::Arbitrary;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Returns a strategy generating matrices with the given dimensions.
+    pub fn gf2_matrix(dimensions: MatrixDimensions) -> impl Strategy<Value = GF2Matrix> {
+        (dimensions.rows, dimensions.cols)
+            .prop_flat_map(|(m, n)| vec(vec(0u8..=1, n), m))
+            .prop_map(GF2Matrix::new)
+    }
+
+    /// Returns a strategy generating packed matrices with the given dimensions.
+    pub fn packed_gf2_matrix<T: Number + Debug>(
+        dimensions: MatrixDimensions,
+    ) -> impl Strategy<Value = PackedGF2Matrix<T>> {
+        (dimensions.rows, packed_cols::<T>(&dimensions.cols))
+            .prop_flat_map(|(m, n)| (vec(vec(any::<bool>(), n), m), Just(n)))
+            .prop_map(|(rows, n)| {
+                PackedGF2Matrix::new(rows.iter().map(|row| pack(row)).collect(), n)
+            })
+    }
+
+    impl Arbitrary for GF2Matrix {
+        type Parameters = MatrixDimensions;
+        type Strategy = BoxedStrategy<GF2Matrix>;
+
+        fn arbitrary_with(dimensions: MatrixDimensions) -> Self::Strategy {
+            gf2_matrix(dimensions).boxed()
+        }
+    }
+
+    impl<T: Number + Debug + 'static> Arbitrary for PackedGF2Matrix<T> {
+        type Parameters = MatrixDimensions;
+        type Strategy = BoxedStrategy<PackedGF2Matrix<T>>;
+
+        fn arbitrary_with(dimensions: MatrixDimensions) -> Self::Strategy {
+            packed_gf2_matrix(dimensions).boxed()
+        }
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck_impls {
+    use quickcheck::{Arbitrary, Gen};
+
+    use super::*;
+    use crate::matrix::MatrixCommon;
+
+    /// Returns a number in `range`, no larger than the size of `g` if possible.
+    fn dimension(g: &mut Gen, range: &RangeInclusive<usize>) -> usize {
+        let end = (*range.end()).min(g.size()).max(*range.start());
+        range.start() + usize::arbitrary(g) % (end - range.start() + 1)
+    }
+
+    /// Returns the matrices obtained by dropping the last row or column.
+    fn shrink_rows(rows: &[Vec<bool>]) -> Vec<Vec<Vec<bool>>> {
+        let mut smaller = Vec::new();
+        if rows.len() > 1 {
+            smaller.push(rows[..rows.len() - 1].to_vec());
+        }
+        if rows.first().is_some_and(|row| row.len() > 1) {
+            smaller.push(
+                rows.iter()
+                    .map(|row| row[..row.len() - 1].to_vec())
+                    .collect(),
+            );
+        }
+        smaller
+    }
+
+    impl Arbitrary for GF2Matrix {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let dimensions = MatrixDimensions::default();
+            let m = dimension(g, &dimensions.rows);
+            let n = dimension(g, &dimensions.cols);
+            GF2Matrix::new(
+                (0..m)
+                    .map(|_| (0..n).map(|_| bool::arbitrary(g) as u8).collect())
+                    .collect(),
+            )
+        }
+
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            let rows: Vec<Vec<bool>> = self
+                .elements
+                .iter()
+                .map(|row| row.iter().map(|&x| x == 1).collect())
+                .collect();
+            Box::new(shrink_rows(&rows).into_iter().map(|rows| {
+                GF2Matrix::new(
+                    rows.iter()
+                        .map(|row| row.iter().map(|&x| x as u8).collect())
+                        .collect(),
+                )
+            }))
+        }
+    }
+
+    impl<T: Number + 'static> Arbitrary for PackedGF2Matrix<T> {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let dimensions = MatrixDimensions::default();
+            let m = dimension(g, &dimensions.rows);
+            let n = dimension(g, &packed_cols::<T>(&dimensions.cols));
+            let rows = (0..m)
+                .map(|_| {
+                    pack(
+                        (0..n)
+                            .map(|_| bool::arbitrary(g))
+                            .collect::<Vec<_>>()
+                            .as_slice(),
+                    )
+                })
+                .collect();
+            PackedGF2Matrix::new(rows, n)
+        }
+
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            let dense = GF2Matrix::from(self);
+            let rows: Vec<Vec<bool>> = (0..dense.nrows())
+                .map(|i| dense.row(i).iter().map(|&x| x == 1).collect())
+                .collect();
+            Box::new(shrink_rows(&rows).into_iter().map(|rows| {
+                let n = rows.first().map_or(0, |row| row.len());
+                PackedGF2Matrix::new(rows.iter().map(|row| pack(row)).collect(), n)
+            }))
+        }
+    }
+}
+
+#[cfg(all(test, feature = "proptest"))]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::matrix::{MatrixCommon, MatrixTrait};
+    use crate::packed_gf2_matrix::BitOrder;
+    use crate::sparse_gf2_matrix::SparseGF2Matrix;
+
+    /// Replays recorded row operations `(i, j)`, meaning `R_i += R_j`.
+    fn replay(mut rows: Vec<Vec<u8>>, operations: &[(usize, usize)]) -> Vec<Vec<u8>> {
+        for &(i, j) in operations {
+            let source = rows[j].clone();
+            for (x, y) in rows[i].iter_mut().zip(source) {
+                *x ^= y;
+            }
+        }
+        rows
+    }
+
+    proptest! {
+        #[test]
+        fn rank_nullity(a in any::<GF2Matrix>()) {
+            prop_assert_eq!(a.rank() + a.kernel().len(), a.ncols());
+        }
+
+        #[test]
+        fn kernel_vectors_are_annihilated(a in any::<GF2Matrix>()) {
+            for k in a.kernel() {
+                for row in &a.elements {
+                    let dot = row.iter().zip(&k).fold(0, |acc, (x, y)| acc ^ (x & y));
+                    prop_assert_eq!(dot, 0);
+                }
+            }
+        }
+
+        #[test]
+        fn packed_kernel_vectors_are_annihilated(a in any::<PackedGF2Matrix<u32>>()) {
+            for k in a.kernel() {
+                for i in 0..a.nrows() {
+                    prop_assert_eq!((a.row(i) & k).count_ones() % 2, 0);
+                }
+            }
+        }
+
+        #[test]
+        fn echelon_operations_replay_to_rref(a in any::<GF2Matrix>()) {
+            let (rref, operations) = a.echelon_form();
+            prop_assert!(rref.is_reduced_echelon());
+            prop_assert_eq!(&replay(a.elements.clone(), &operations), &rref.elements);
+
+            let packed = PackedGF2Matrix::<u16>::from(&a);
+            let (packed_rref, packed_operations) = packed.echelon_form();
+            prop_assert_eq!(&packed_operations, &operations);
+            prop_assert_eq!(GF2Matrix::from(&packed_rref).elements, rref.elements);
+        }
+
+        #[test]
+        fn representations_agree(a in any::<GF2Matrix>()) {
+            let packed = PackedGF2Matrix::<u64>::from(&a);
+            prop_assert_eq!(packed.rank(), a.rank());
+            let kernel: Vec<Vec<u8>> = packed
+                .kernel()
+                .into_iter()
+                .map(|k| PackedGF2Matrix::new(vec![k], a.ncols()))
+                .map(|k| GF2Matrix::from(&k).elements.remove(0))
+                .collect();
+            prop_assert_eq!(kernel, a.kernel());
+            let image = GF2Matrix::from(&PackedGF2Matrix::new(packed.image(), a.ncols()));
+            prop_assert_eq!(image.elements, a.image());
+
+            let sparse = SparseGF2Matrix::from(&a);
+            prop_assert_eq!(sparse.rank(), a.rank());
+            prop_assert_eq!(GF2Matrix::from(&sparse).elements, a.elements.clone());
+            prop_assert_eq!(PackedGF2Matrix::<u64>::from(&sparse), packed);
+        }
+
+        #[test]
+        fn wide_packed_matrices_agree(
+            a in gf2_matrix(MatrixDimensions { rows: 1..=32, cols: 65..=128 })
+        ) {
+            let packed = PackedGF2Matrix::<u128>::from(&a);
+            prop_assert_eq!(GF2Matrix::from(&packed).elements, a.elements.clone());
+            prop_assert_eq!(packed.rank(), a.rank());
+            let (packed_rref, _) = packed.echelon_form();
+            prop_assert_eq!(GF2Matrix::from(&packed_rref).elements, a.echelon_form().0.elements);
+            prop_assert_eq!(packed.kernel().len(), a.kernel().len());
+        }
+
+        #[test]
+        fn conversions_agree_for_both_bit_orders(a in any::<PackedGF2Matrix<u16>>()) {
+            let msb = a.from_int_matrix_to_gf2_matrix(BitOrder::MSB);
+            let lsb = a.from_int_matrix_to_gf2_matrix(BitOrder::LSB);
+            prop_assert_eq!(&GF2Matrix::from(&a).elements, &msb.elements);
+            prop_assert_eq!(&GF2Matrix::from(a.clone()).elements, &msb.elements);
+            for (m, l) in msb.elements.iter().zip(&lsb.elements) {
+                prop_assert_eq!(m.iter().rev().collect::<Vec<_>>(), l.iter().collect::<Vec<_>>());
+            }
+            prop_assert_eq!(PackedGF2Matrix::<u16>::from(&msb), a.clone());
+            prop_assert_eq!(PackedGF2Matrix::<u16>::from(msb), a);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "quickcheck"))]
+mod quickcheck_tests {
+    use super::*;
+    use crate::matrix::MatrixTrait;
+
+    #[test]
+    fn quickcheck_matrices_have_consistent_ranks() {
+        fn ranks_agree(a: PackedGF2Matrix<u16>, b: GF2Matrix) -> bool {
+            a.rank() == GF2Matrix::from(&a).rank()
+                && b.rank() == PackedGF2Matrix::<u16>::from(&b).rank()
+        }
+        quickcheck::quickcheck(ranks_agree as fn(PackedGF2Matrix<u16>, GF2Matrix) -> bool);
+    }
+}
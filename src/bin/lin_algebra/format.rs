@@ -0,0 +1,323 @@
+//! Matrix file formats.
+//!
+//! - `text`: one row per line of `0` and `1` characters. Whitespace and commas
+//!   between entries, blank lines and lines starting with `#` are ignored.
+//! - `hex`: one row per line of hexadecimal digits, with an optional `0x`
+//!   prefix. Column 0 is the most significant bit. Without `--cols`, every
+//!   row has four columns per digit.
+//! - `binary`: the number of rows and the number of columns as little-endian
+//!   `u64`, followed by each row packed in `ceil(ncols / 8)` bytes, column 0
+//!   being the most significant bit of the first byte.
+
+use std::io::{Read, Write};
+
+use clap::ValueEnum;
+use lin_algebra::GF2Matrix;
+
+/// Encoding of a matrix file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Rows of `0` and `1` characters.
+    Text,
+    /// Rows of hexadecimal digits.
+    Hex,
+    /// Packed binary rows after a header holding the shape.
+    Binary,
+}
+
+/// A matrix read from or written to a file.
+///
+/// Unlike `GF2Matrix`, it keeps its number of columns when it has no rows,
+/// such as the kernel basis of an injective map.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatrixData {
+    pub rows: Vec<Vec<u8>>,
+    pub ncols: usize,
+}
+
+impl MatrixData {
+    /// Creates a matrix from rows of `ncols` bits each.
+    pub fn new(rows: Vec<Vec<u8>>, ncols: usize) -> Self {
+        debug_assert!(rows.iter().all(|row| row.len() == ncols));
+        Self { rows, ncols }
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn to_gf2_matrix(&self) -> GF2Matrix {
+        GF2Matrix::new(self.rows.clone())
+    }
+}
+
+impl From<GF2Matrix> for MatrixData {
+    fn from(matrix: GF2Matrix) -> Self {
+        let ncols = matrix.elements.first().map_or(0, |row| row.len());
+        Self::new(matrix.elements, ncols)
+    }
+}
+
+/// Reads a matrix in `format`.
+///
+/// `ncols` fixes the number of columns of hexadecimal rows.
+pub fn read_matrix(
+    mut reader: impl Read,
+    format: Format,
+    ncols: Option<usize>,
+) -> Result<MatrixData, String> {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .map_err(|error| format!("cannot read input: {error}"))?;
+
+    match format {
+        Format::Text => parse_text(&utf8(&bytes)?),
+        Format::Hex => parse_hex(&utf8(&bytes)?, ncols),
+        Format::Binary => parse_binary(&bytes),
+    }
+}
+
+/// Writes a matrix in `format`.
+pub fn write_matrix(
+    mut writer: impl Write,
+    matrix: &MatrixData,
+    format: Format,
+) -> Result<(), String> {
+    let bytes = match format {
+        Format::Text => matrix
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&bit| char::from(b'0' + bit))
+                    .collect::<String>()
+                    + "\n"
+            })
+            .collect::<String>()
+            .into_bytes(),
+        Format::Hex => matrix
+            .rows
+            .iter()
+            .map(|row| to_hex(row) + "\n")
+            .collect::<String>()
+            .into_bytes(),
+        Format::Binary => to_binary(matrix),
+    };
+    writer
+        .write_all(&bytes)
+        .map_err(|error| format!("cannot write output: {error}"))
+}
+
+fn utf8(bytes: &[u8]) -> Result<String, String> {
+    String::from_utf8(bytes.to_vec()).map_err(|_| "input is not valid UTF-8 text".to_string())
+}
+
+/// Returns the numbered lines holding a row.
+fn content_lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// Checks that every row has `ncols` entries and builds the matrix.
+fn rectangular(rows: Vec<(usize, Vec<u8>)>) -> Result<MatrixData, String> {
+    let ncols = rows.first().map_or(0, |(_, row)| row.len());
+    if let Some((line, row)) = rows.iter().find(|(_, row)| row.len() != ncols) {
+        return Err(format!(
+            "line {line}: row has {} columns, expected {ncols}",
+            row.len()
+        ));
+    }
+    Ok(MatrixData::new(
+        rows.into_iter().map(|(_, row)| row).collect(),
+        ncols,
+    ))
+}
+
+fn parse_text(text: &str) -> Result<MatrixData, String> {
+    let rows = content_lines(text)
+        .map(|(line, content)| {
+            content
+                .chars()
+                .filter(|c| !c.is_whitespace() && *c != ',')
+                .map(|c| match c {
+                    '0' => Ok(0),
+                    '1' => Ok(1),
+                    _ => Err(format!("line {line}: invalid entry `{c}`")),
+                })
+                .collect::<Result<Vec<u8>, String>>()
+                .map(|row| (line, row))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    rectangular(rows)
+}
+
+fn parse_hex(text: &str, ncols: Option<usize>) -> Result<MatrixData, String> {
+    let rows = content_lines(text)
+        .map(|(line, content)| {
+            let digits = content
+                .strip_prefix("0x")
+                .or_else(|| content.strip_prefix("0X"))
+                .unwrap_or(content);
+            let mut row = Vec::with_capacity(4 * digits.len());
+            for c in digits.chars() {
+                let value = c
+                    .to_digit(16)
+                    .ok_or_else(|| format!("line {line}: invalid hexadecimal digit `{c}`"))?;
+                row.extend((0..4).rev().map(|bit| ((value >> bit) & 1) as u8));
+            }
+            if let Some(ncols) = ncols {
+                row = resize_row(row, ncols)
+                    .ok_or_else(|| format!("line {line}: row does not fit in {ncols} columns"))?;
+            }
+            Ok((line, row))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    rectangular(rows)
+}
+
+/// Pads `row` with leading zeros, or drops leading zeros, to `ncols` entries.
+fn resize_row(row: Vec<u8>, ncols: usize) -> Option<Vec<u8>> {
+    if row.len() >= ncols {
+        let extra = row.len() - ncols;
+        row[..extra]
+            .iter()
+            .all(|&bit| bit == 0)
+            .then(|| row[extra..].to_vec())
+    } else {
+        let mut padded = vec![0; ncols - row.len()];
+        padded.extend(row);
+        Some(padded)
+    }
+}
+
+fn to_hex(row: &[u8]) -> String {
+    let padding = (4 - row.len() % 4) % 4;
+    let bits: Vec<u8> = std::iter::repeat_n(0, padding)
+        .chain(row.iter().copied())
+        .collect();
+    bits.chunks(4)
+        .map(|nibble| {
+            let value = nibble
+                .iter()
+                .fold(0, |value, &bit| (value << 1) | u32::from(bit));
+            char::from_digit(value, 16).unwrap()
+        })
+        .collect()
+}
+
+fn parse_binary(bytes: &[u8]) -> Result<MatrixData, String> {
+    let header = |index: usize| -> Result<usize, String> {
+        let field = bytes
+            .get(8 * index..8 * index + 8)
+            .ok_or_else(|| "binary input is shorter than its header".to_string())?;
+        usize::try_from(u64::from_le_bytes(field.try_into().unwrap()))
+            .map_err(|_| "binary input is too large".to_string())
+    };
+    let (nrows, ncols) = (header(0)?, header(1)?);
+    let row_bytes = ncols.div_ceil(8);
+    let body = &bytes[16..];
+
+    // Rows of no columns take no bytes, so their number is not bounded by the
+    // size of the input.
+    if ncols == 0 && nrows > 0 {
+        return Err(format!("binary input has {nrows} rows but no columns"));
+    }
+    if nrows.checked_mul(row_bytes) != Some(body.len()) {
+        return Err(format!(
+            "binary input holds {} bytes of rows, expected {nrows} rows of {row_bytes} bytes",
+            body.len()
+        ));
+    }
+
+    let rows = (0..nrows)
+        .map(|row| {
+            let packed = &body[row * row_bytes..(row + 1) * row_bytes];
+            (0..ncols)
+                .map(|col| (packed[col / 8] >> (7 - col % 8)) & 1)
+                .collect()
+        })
+        .collect();
+    Ok(MatrixData::new(rows, ncols))
+}
+
+fn to_binary(matrix: &MatrixData) -> Vec<u8> {
+    let row_bytes = matrix.ncols.div_ceil(8);
+    let mut bytes = Vec::with_capacity(16 + matrix.nrows() * row_bytes);
+    bytes.extend((matrix.nrows() as u64).to_le_bytes());
+    bytes.extend((matrix.ncols as u64).to_le_bytes());
+    for row in &matrix.rows {
+        let mut packed = vec![0u8; row_bytes];
+        for (col, &bit) in row.iter().enumerate() {
+            packed[col / 8] |= bit << (7 - col % 8);
+        }
+        bytes.extend(packed);
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> MatrixData {
+        MatrixData::new(
+            vec![
+                vec![1, 0, 1, 1, 0, 0, 0, 0, 0, 1],
+                vec![0, 1, 1, 0, 1, 0, 1, 1, 1, 0],
+            ],
+            10,
+        )
+    }
+
+    #[test]
+    fn test_formats_round_trip() {
+        for format in [Format::Text, Format::Hex, Format::Binary] {
+            let mut bytes = Vec::new();
+            write_matrix(&mut bytes, &sample(), format).unwrap();
+            let read = read_matrix(bytes.as_slice(), format, Some(10)).unwrap();
+            assert_eq!(read, sample(), "{format:?}");
+        }
+
+        let mut bytes = Vec::new();
+        write_matrix(&mut bytes, &sample(), Format::Hex).unwrap();
+        assert_eq!(String::from_utf8(bytes).unwrap(), "2c1\n1ae\n");
+
+        let empty = MatrixData::new(vec![], 7);
+        let mut bytes = Vec::new();
+        write_matrix(&mut bytes, &empty, Format::Binary).unwrap();
+        assert_eq!(
+            read_matrix(bytes.as_slice(), Format::Binary, None).unwrap(),
+            empty
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_the_line() {
+        let text = "# comment\n1 0 1\n\n1,1,0\n0 2 1\n";
+        assert_eq!(
+            read_matrix(text.as_bytes(), Format::Text, None).unwrap_err(),
+            "line 5: invalid entry `2`"
+        );
+        assert_eq!(
+            read_matrix("101\n11\n".as_bytes(), Format::Text, None).unwrap_err(),
+            "line 2: row has 2 columns, expected 3"
+        );
+        assert_eq!(
+            read_matrix("0x1f\n".as_bytes(), Format::Hex, Some(4)).unwrap_err(),
+            "line 1: row does not fit in 4 columns"
+        );
+        assert!(read_matrix([1u8, 0].as_slice(), Format::Binary, None).is_err());
+
+        let header: Vec<u8> = [1u64 << 40, 0]
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect();
+        assert_eq!(
+            read_matrix(header.as_slice(), Format::Binary, None).unwrap_err(),
+            "binary input has 1099511627776 rows but no columns"
+        );
+    }
+}
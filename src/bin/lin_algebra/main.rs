@@ -0,0 +1,308 @@
+//! Command-line tool for linear algebra over GF(2) on matrix files.
+//!
+//! Matrices of at most 64 columns are processed as `PackedGF2Matrix<u64>`,
+//! larger ones as `GF2Matrix`.
+
+mod format;
+
+use std::fs::File;
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use lin_algebra::matrix::MatrixTrait;
+use lin_algebra::packed_gf2_matrix::PackedGF2Matrix;
+use lin_algebra::GF2Matrix;
+
+use format::{read_matrix, write_matrix, Format, MatrixData};
+
+/// Largest number of columns processed as a packed matrix.
+const PACKED_MAX_COLUMNS: usize = u64::BITS as usize;
+
+#[derive(Parser)]
+#[command(version, about = "Linear algebra over GF(2) on matrix files")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Format of the input matrices.
+    #[arg(long, value_enum, global = true, default_value = "text")]
+    from: Format,
+
+    /// Format of the output matrix.
+    #[arg(long, value_enum, global = true, default_value = "text")]
+    to: Format,
+
+    /// Number of columns of hexadecimal input rows [default: four per digit].
+    #[arg(long, global = true)]
+    cols: Option<usize>,
+
+    /// Output file [default: standard output].
+    #[arg(short, long, global = true)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the rank of a matrix.
+    Rank(Input),
+    /// Write a basis of the kernel, one vector per row.
+    Kernel(Input),
+    /// Write a basis of the image (row space), one vector per row.
+    Image(Input),
+    /// Write the reduced row echelon form.
+    Echelon(Input),
+    /// Solve `A x = b` for every right-hand side `b`, one solution per row.
+    Solve {
+        #[command(flatten)]
+        input: Input,
+        /// File holding one right-hand side per row.
+        #[arg(long)]
+        rhs: PathBuf,
+    },
+    /// Write the inverse of a square matrix.
+    Inverse(Input),
+    /// Convert a matrix between formats.
+    Convert(Input),
+}
+
+#[derive(clap::Args)]
+struct Input {
+    /// Input file [default: standard input].
+    input: Option<PathBuf>,
+}
+
+/// Output of a command.
+enum Output {
+    Number(usize),
+    Matrix(MatrixData),
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("lin_algebra: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<(), String> {
+    let read = |path: &Option<PathBuf>| match path {
+        Some(path) => {
+            let file = File::open(path)
+                .map_err(|error| format!("cannot open {}: {error}", path.display()))?;
+            read_matrix(file, cli.from, cli.cols)
+        }
+        None => read_matrix(io::stdin().lock(), cli.from, cli.cols),
+    };
+
+    let output = match &cli.command {
+        Command::Rank(input) => Output::Number(rank(&read(&input.input)?)),
+        Command::Kernel(input) => Output::Matrix(kernel(&read(&input.input)?)),
+        Command::Image(input) => Output::Matrix(image(&read(&input.input)?)),
+        Command::Echelon(input) => Output::Matrix(echelon(&read(&input.input)?)),
+        Command::Solve { input, rhs } => {
+            Output::Matrix(solve(&read(&input.input)?, &read(&Some(rhs.clone()))?)?)
+        }
+        Command::Inverse(input) => Output::Matrix(inverse(&read(&input.input)?)?),
+        Command::Convert(input) => Output::Matrix(read(&input.input)?),
+    };
+
+    let write = |writer: &mut dyn io::Write| match &output {
+        Output::Number(value) => {
+            writeln!(writer, "{value}").map_err(|error| format!("cannot write output: {error}"))
+        }
+        Output::Matrix(matrix) => write_matrix(writer, matrix, cli.to),
+    };
+    match &cli.output {
+        Some(path) => write(
+            &mut File::create(path)
+                .map_err(|error| format!("cannot create {}: {error}", path.display()))?,
+        ),
+        None => write(&mut io::stdout().lock()),
+    }
+}
+
+fn to_packed(matrix: &MatrixData) -> PackedGF2Matrix<u64> {
+    matrix.to_gf2_matrix().view().to_packed()
+}
+
+fn from_packed(matrix: PackedGF2Matrix<u64>) -> MatrixData {
+    MatrixData::new(GF2Matrix::from(&matrix).elements, matrix.ncols())
+}
+
+fn rank(matrix: &MatrixData) -> usize {
+    if matrix.nrows() == 0 {
+        0
+    } else if matrix.ncols <= PACKED_MAX_COLUMNS {
+        to_packed(matrix).rank()
+    } else {
+        matrix.to_gf2_matrix().rank()
+    }
+}
+
+fn echelon(matrix: &MatrixData) -> MatrixData {
+    if matrix.nrows() == 0 {
+        matrix.clone()
+    } else if matrix.ncols <= PACKED_MAX_COLUMNS {
+        from_packed(to_packed(matrix).echelon_form().0)
+    } else {
+        matrix.to_gf2_matrix().echelon_form().0.into()
+    }
+}
+
+fn kernel(matrix: &MatrixData) -> MatrixData {
+    if matrix.nrows() == 0 {
+        GF2Matrix::identity(matrix.ncols).into()
+    } else if matrix.ncols <= PACKED_MAX_COLUMNS {
+        from_packed(PackedGF2Matrix::new(
+            to_packed(matrix).kernel(),
+            matrix.ncols,
+        ))
+    } else {
+        MatrixData::new(matrix.to_gf2_matrix().kernel(), matrix.ncols)
+    }
+}
+
+fn image(matrix: &MatrixData) -> MatrixData {
+    if matrix.nrows() == 0 {
+        matrix.clone()
+    } else if matrix.ncols <= PACKED_MAX_COLUMNS {
+        from_packed(PackedGF2Matrix::new(
+            to_packed(matrix).image(),
+            matrix.ncols,
+        ))
+    } else {
+        MatrixData::new(matrix.to_gf2_matrix().image(), matrix.ncols)
+    }
+}
+
+fn transpose(matrix: &MatrixData) -> MatrixData {
+    let rows = (0..matrix.ncols)
+        .map(|col| matrix.rows.iter().map(|row| row[col]).collect())
+        .collect();
+    MatrixData::new(rows, matrix.nrows())
+}
+
+/// Returns the product `a * b`.
+fn multiply(a: &MatrixData, b: &MatrixData) -> MatrixData {
+    let rows = a
+        .rows
+        .iter()
+        .map(|row| {
+            (0..b.ncols)
+                .map(|col| {
+                    row.iter()
+                        .zip(&b.rows)
+                        .fold(0, |sum, (&x, b_row)| sum ^ (x & b_row[col]))
+                })
+                .collect()
+        })
+        .collect();
+    MatrixData::new(rows, b.ncols)
+}
+
+/// Solves `a * x = b` for every row `b` of `rhs`, returning the solutions as
+/// rows.
+fn solve(a: &MatrixData, rhs: &MatrixData) -> Result<MatrixData, String> {
+    if rhs.nrows() > 0 && rhs.ncols != a.nrows() {
+        return Err(format!(
+            "right-hand sides have {} entries, expected {}",
+            rhs.ncols,
+            a.nrows()
+        ));
+    }
+    if a.nrows() == 0 || rank(a) < a.ncols {
+        return Err("matrix does not have full column rank".to_string());
+    }
+    if rhs.nrows() == 0 {
+        return Ok(MatrixData::new(vec![], a.ncols));
+    }
+
+    let y = transpose(rhs);
+    let x = MatrixData::from(a.to_gf2_matrix().solve_matrix_system(&y.to_gf2_matrix()));
+    if multiply(a, &x) != y {
+        return Err("linear system is inconsistent".to_string());
+    }
+    Ok(transpose(&x))
+}
+
+fn inverse(matrix: &MatrixData) -> Result<MatrixData, String> {
+    if matrix.nrows() != matrix.ncols {
+        return Err(format!(
+            "matrix is not square: {}x{}",
+            matrix.nrows(),
+            matrix.ncols
+        ));
+    }
+    if rank(matrix) < matrix.ncols {
+        return Err("matrix is singular".to_string());
+    }
+    let identity = GF2Matrix::identity(matrix.ncols);
+    Ok(matrix.to_gf2_matrix().solve_matrix_system(&identity).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(rows: &[&str]) -> MatrixData {
+        let rows: Vec<Vec<u8>> = rows
+            .iter()
+            .map(|row| row.bytes().map(|c| c - b'0').collect())
+            .collect();
+        let ncols = rows[0].len();
+        MatrixData::new(rows, ncols)
+    }
+
+    #[test]
+    fn test_packed_and_dense_paths_agree() {
+        // The same matrix padded with zero columns beyond the packed width.
+        let narrow = data(&["1101", "0110", "1011"]);
+        let padding = "0".repeat(PACKED_MAX_COLUMNS);
+        let wide = MatrixData::new(
+            narrow
+                .rows
+                .iter()
+                .map(|row| [row.clone(), vec![0; padding.len()]].concat())
+                .collect(),
+            narrow.ncols + padding.len(),
+        );
+
+        assert_eq!(rank(&narrow), 2);
+        assert_eq!(rank(&wide), 2);
+        assert_eq!(
+            transpose(&echelon(&wide)).rows[..4],
+            transpose(&echelon(&narrow)).rows[..]
+        );
+        assert_eq!(kernel(&narrow).nrows(), 2);
+        assert_eq!(kernel(&wide).nrows(), 2 + padding.len());
+        assert_eq!(image(&narrow).nrows(), image(&wide).nrows());
+    }
+
+    #[test]
+    fn test_solve_and_inverse() {
+        let a = data(&["100", "010", "001", "111"]);
+        assert_eq!(solve(&a, &data(&["1010"])).unwrap(), data(&["101"]));
+        assert_eq!(
+            solve(&a, &data(&["1011"])).unwrap_err(),
+            "linear system is inconsistent"
+        );
+        assert_eq!(
+            solve(&data(&["11", "11"]), &data(&["00"])).unwrap_err(),
+            "matrix does not have full column rank"
+        );
+
+        let m = data(&["110", "011", "001"]);
+        let inv = inverse(&m).unwrap();
+        assert_eq!(multiply(&m, &inv), MatrixData::from(GF2Matrix::identity(3)));
+        assert_eq!(
+            inverse(&data(&["11", "11"])).unwrap_err(),
+            "matrix is singular"
+        );
+    }
+}
@@ -0,0 +1,463 @@
+use crate::error::MatrixError;
+use crate::matrix::{MatrixCommon, Number};
+use crate::packed_gf2_matrix::PackedGF2Matrix;
+use crate::view::GF2Storage;
+use crate::GF2Matrix;
+use alloc::{vec, vec::Vec};
+
+/// Returns an error if `ncols` columns do not fit in a word of type `T`.
+fn check_width<T: Number>(ncols: usize) -> Result<(), MatrixError> {
+    let max = core::mem::size_of::<T>() * 8;
+    if ncols > max {
+        return Err(MatrixError::TooManyColumns { ncols, max });
+    }
+    Ok(())
+}
+
+/// Checks that the blocks of every block row have the same number of rows and
+/// the block columns of `blocks` have consistent widths, and returns an error
+/// naming the first offending pair of blocks otherwise.
+fn check_block_shapes<M: GF2Storage, const C: usize>(
+    blocks: &[[&M; C]],
+) -> Result<(), MatrixError> {
+    for row in blocks {
+        for block in row.iter().skip(1) {
+            if row[0].shape().0 != block.shape().0 {
+                return Err(MatrixError::DimensionMismatch {
+                    operation: "block",
+                    left: row[0].shape(),
+                    right: block.shape(),
+                });
+            }
+        }
+    }
+    for row in blocks.iter().skip(1) {
+        for (first, block) in blocks[0].iter().zip(row) {
+            if first.shape().1 != block.shape().1 {
+                return Err(MatrixError::DimensionMismatch {
+                    operation: "block",
+                    left: first.shape(),
+                    right: block.shape(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+impl GF2Matrix {
+    /// Returns the `m x n` zero matrix.
+    pub fn zeros(m: usize, n: usize) -> Self {
+        GF2Matrix::new(vec![vec![0; n]; m])
+    }
+
+    /// Returns the `m x n` matrix whose entries are all `1`.
+    pub fn ones(m: usize, n: usize) -> Self {
+        GF2Matrix::new(vec![vec![1; n]; m])
+    }
+
+    /// Returns the `n x n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        let mut result = Self::zeros(n, n);
+        for i in 0..n {
+            result.elements[i][i] = 1;
+        }
+        result
+    }
+
+    /// Concatenates `other` to the right of this matrix, `[self | other]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MatrixError::DimensionMismatch`] if the numbers of rows differ.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_matrix::GF2Matrix;
+    /// let a = GF2Matrix::new(vec![vec![1, 1, 0], vec![0, 1, 1]]);
+    ///
+    /// // Systematic generator matrix [I | A].
+    /// let g = GF2Matrix::identity(2).hstack(&a).unwrap();
+    /// assert_eq!(g.elements, vec![vec![1, 0, 1, 1, 0], vec![0, 1, 0, 1, 1]]);
+    /// ```
+    pub fn hstack(&self, other: &GF2Matrix) -> Result<GF2Matrix, MatrixError> {
+        if self.nrows() != other.nrows() {
+            return Err(MatrixError::DimensionMismatch {
+                operation: "hstack",
+                left: self.shape(),
+                right: other.shape(),
+            });
+        }
+        let elements = self
+            .elements
+            .iter()
+            .zip(&other.elements)
+            .map(|(a, b)| a.iter().chain(b).copied().collect())
+            .collect();
+        Ok(GF2Matrix::new(elements))
+    }
+
+    /// Stacks `other` below this matrix.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MatrixError::DimensionMismatch`] if the numbers of columns
+    /// differ. A matrix without rows can be stacked with any matrix.
+    pub fn vstack(&self, other: &GF2Matrix) -> Result<GF2Matrix, MatrixError> {
+        if self.nrows() > 0 && other.nrows() > 0 && self.ncols() != other.ncols() {
+            return Err(MatrixError::DimensionMismatch {
+                operation: "vstack",
+                left: self.shape(),
+                right: other.shape(),
+            });
+        }
+        let elements = self
+            .elements
+            .iter()
+            .chain(&other.elements)
+            .cloned()
+            .collect();
+        Ok(GF2Matrix::new(elements))
+    }
+
+    /// Assembles a matrix from a grid of blocks, e.g. `[[A, B], [C, D]]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MatrixError::DimensionMismatch`] if the blocks of a block row
+    /// have different numbers of rows, or the blocks of a block column have
+    /// different numbers of columns.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_matrix::GF2Matrix;
+    /// let a = GF2Matrix::new(vec![vec![1, 1]]);
+    /// let i = GF2Matrix::identity(2);
+    /// let z = GF2Matrix::zeros(1, 2);
+    ///
+    /// let m = GF2Matrix::block(&[[&a, &z], [&i, &i]]).unwrap();
+    /// assert_eq!(
+    ///     m.elements,
+    ///     vec![vec![1, 1, 0, 0], vec![1, 0, 1, 0], vec![0, 1, 0, 1]]
+    /// );
+    /// ```
+    pub fn block<const C: usize>(blocks: &[[&GF2Matrix; C]]) -> Result<GF2Matrix, MatrixError> {
+        check_block_shapes(blocks)?;
+        let mut result = GF2Matrix::new(Vec::new());
+        for row in blocks {
+            let mut block_row =
+                GF2Matrix::new(vec![Vec::new(); row.first().map_or(0, |b| b.nrows())]);
+            for block in row {
+                block_row = block_row.hstack(block)?;
+            }
+            result = result.vstack(&block_row)?;
+        }
+        Ok(result)
+    }
+
+    /// Returns the Kronecker product of this matrix with `other`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_matrix::GF2Matrix;
+    /// // Generator of the first-order Reed–Muller code RM(1, 2).
+    /// let g = GF2Matrix::new(vec![vec![1, 1], vec![0, 1]]);
+    /// let rm = g.kron(&g);
+    ///
+    /// assert_eq!(rm.elements[0], vec![1, 1, 1, 1]);
+    /// assert_eq!(rm.elements[3], vec![0, 0, 0, 1]);
+    /// ```
+    pub fn kron(&self, other: &GF2Matrix) -> GF2Matrix {
+        let mut elements = Vec::with_capacity(self.nrows() * other.nrows());
+        for a in &self.elements {
+            for b in &other.elements {
+                elements.push(
+                    a.iter()
+                        .flat_map(|&x| b.iter().map(move |&y| x & y))
+                        .collect(),
+                );
+            }
+        }
+        GF2Matrix::new(elements)
+    }
+
+    /// Returns the block diagonal matrix `[[self, 0], [0, other]]`.
+    pub fn direct_sum(&self, other: &GF2Matrix) -> GF2Matrix {
+        let (m, n) = self.shape();
+        let (p, q) = other.shape();
+        let mut result = Self::zeros(m + p, n + q);
+        for (i, row) in self.elements.iter().enumerate() {
+            result.elements[i][..n].copy_from_slice(row);
+        }
+        for (i, row) in other.elements.iter().enumerate() {
+            result.elements[m + i][n..].copy_from_slice(row);
+        }
+        result
+    }
+}
+
+impl<T: Number> PackedGF2Matrix<T> {
+    /// Returns the `m x n` zero matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` columns do not fit in `T`.
+    pub fn zeros(m: usize, n: usize) -> Self {
+        assert!(
+            n <= core::mem::size_of::<T>() * 8,
+            "{} columns do not fit in the packed type",
+            n
+        );
+        Self::new(vec![T::zero(); m], n)
+    }
+
+    /// Returns the `m x n` matrix whose entries are all `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` columns do not fit in `T`.
+    pub fn ones(m: usize, n: usize) -> Self {
+        assert!(
+            n <= core::mem::size_of::<T>() * 8,
+            "{} columns do not fit in the packed type",
+            n
+        );
+        let row = (0..n).fold(T::zero(), |acc, i| acc ^ (T::one() << i));
+        Self::new(vec![row; m], n)
+    }
+
+    /// Concatenates `other` to the right of this matrix, `[self | other]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MatrixError::DimensionMismatch`] if the numbers of rows
+    /// differ, and [`MatrixError::TooManyColumns`] if the result does not fit
+    /// in `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::packed_gf2_matrix::PackedGF2Matrix;
+    /// let a = PackedGF2Matrix::new(vec![0b110u8, 0b011], 3);
+    ///
+    /// let g = PackedGF2Matrix::identity(2).hstack(&a).unwrap();
+    /// assert_eq!(g, PackedGF2Matrix::new(vec![0b10110, 0b01011], 5));
+    /// ```
+    pub fn hstack(&self, other: &PackedGF2Matrix<T>) -> Result<PackedGF2Matrix<T>, MatrixError> {
+        if self.nrows() != other.nrows() {
+            return Err(MatrixError::DimensionMismatch {
+                operation: "hstack",
+                left: self.shape(),
+                right: other.shape(),
+            });
+        }
+        let ncols = self.ncols() + other.ncols();
+        check_width::<T>(ncols)?;
+        let elements = (0..self.nrows())
+            .map(|i| {
+                // Shifting by the full width of `T` would overflow.
+                if self.ncols() == 0 {
+                    other.row(i)
+                } else if other.ncols() == 0 {
+                    self.row(i)
+                } else {
+                    (self.row(i) << other.ncols()) ^ other.row(i)
+                }
+            })
+            .collect();
+        Ok(PackedGF2Matrix::new(elements, ncols))
+    }
+
+    /// Stacks `other` below this matrix.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MatrixError::DimensionMismatch`] if the numbers of columns
+    /// differ. A matrix without rows can be stacked with any matrix.
+    pub fn vstack(&self, other: &PackedGF2Matrix<T>) -> Result<PackedGF2Matrix<T>, MatrixError> {
+        if self.nrows() > 0 && other.nrows() > 0 && self.ncols() != other.ncols() {
+            return Err(MatrixError::DimensionMismatch {
+                operation: "vstack",
+                left: self.shape(),
+                right: other.shape(),
+            });
+        }
+        let elements = (0..self.nrows())
+            .map(|i| self.row(i))
+            .chain((0..other.nrows()).map(|i| other.row(i)))
+            .collect();
+        let ncols = if self.nrows() == 0 {
+            other.ncols()
+        } else {
+            self.ncols()
+        };
+        Ok(PackedGF2Matrix::new(elements, ncols))
+    }
+
+    /// Assembles a matrix from a grid of blocks, e.g. `[[A, B], [C, D]]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MatrixError::DimensionMismatch`] if the blocks of a block row
+    /// have different numbers of rows, or the blocks of a block column have
+    /// different numbers of columns, and [`MatrixError::TooManyColumns`] if the
+    /// result does not fit in `T`.
+    pub fn block<const C: usize>(
+        blocks: &[[&PackedGF2Matrix<T>; C]],
+    ) -> Result<PackedGF2Matrix<T>, MatrixError> {
+        check_block_shapes(blocks)?;
+        let ncols = blocks
+            .first()
+            .map_or(0, |row| row.iter().map(|b| b.ncols()).sum());
+        check_width::<T>(ncols)?;
+        let mut result = PackedGF2Matrix::new(Vec::new(), ncols);
+        for row in blocks {
+            let nrows = row.first().map_or(0, |b| b.nrows());
+            let mut block_row = PackedGF2Matrix::new(vec![T::zero(); nrows], 0);
+            for block in row {
+                block_row = block_row.hstack(block)?;
+            }
+            result = result.vstack(&block_row)?;
+        }
+        Ok(result)
+    }
+
+    /// Returns the Kronecker product of this matrix with `other`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MatrixError::TooManyColumns`] if the result does not fit in `T`.
+    pub fn kron(&self, other: &PackedGF2Matrix<T>) -> Result<PackedGF2Matrix<T>, MatrixError> {
+        let (m, n) = self.shape();
+        let (p, q) = other.shape();
+        check_width::<T>(n * q)?;
+        let mut elements = Vec::with_capacity(m * p);
+        for i in 0..m {
+            for k in 0..p {
+                let mut row = T::zero();
+                if q > 0 {
+                    for j in 0..n {
+                        if self.get(i, j) == 1 {
+                            row = row ^ (other.row(k) << ((n - 1 - j) * q));
+                        }
+                    }
+                }
+                elements.push(row);
+            }
+        }
+        Ok(PackedGF2Matrix::new(elements, n * q))
+    }
+
+    /// Returns the block diagonal matrix `[[self, 0], [0, other]]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MatrixError::TooManyColumns`] if the result does not fit in `T`.
+    pub fn direct_sum(
+        &self,
+        other: &PackedGF2Matrix<T>,
+    ) -> Result<PackedGF2Matrix<T>, MatrixError> {
+        let upper = self.hstack(&PackedGF2Matrix::new(
+            vec![T::zero(); self.nrows()],
+            other.ncols(),
+        ))?;
+        let lower =
+            PackedGF2Matrix::new(vec![T::zero(); other.nrows()], self.ncols()).hstack(other)?;
+        upper.vstack(&lower)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> GF2Matrix {
+        GF2Matrix::new(vec![vec![1, 0, 1], vec![0, 1, 1]])
+    }
+
+    #[test]
+    fn test_dense_and_packed_constructions_agree() {
+        let a = sample();
+        let b = GF2Matrix::new(vec![vec![0, 1], vec![1, 1], vec![1, 0]]);
+        let pa = a.view().to_packed::<u64>();
+        let pb = b.view().to_packed::<u64>();
+        let dense = |m: &PackedGF2Matrix<u64>| GF2Matrix::from(m).elements;
+
+        let c = GF2Matrix::identity(3).hstack(&b).unwrap();
+        assert_eq!(
+            dense(&PackedGF2Matrix::identity(3).hstack(&pb).unwrap()),
+            c.elements
+        );
+        assert_eq!(
+            dense(&pa.vstack(&PackedGF2Matrix::ones(1, 3)).unwrap()),
+            a.vstack(&GF2Matrix::ones(1, 3)).unwrap().elements
+        );
+        assert_eq!(dense(&pa.kron(&pb).unwrap()), a.kron(&b).elements);
+        assert_eq!(
+            dense(&pa.direct_sum(&pb).unwrap()),
+            a.direct_sum(&b).elements
+        );
+        assert_eq!(
+            dense(&PackedGF2Matrix::zeros(0, 5).vstack(&pa).unwrap()),
+            GF2Matrix::zeros(0, 5).vstack(&a).unwrap().elements
+        );
+
+        let z = GF2Matrix::zeros(2, 2);
+        let pz = PackedGF2Matrix::zeros(2, 2);
+        let m = GF2Matrix::block(&[[&a, &z], [&GF2Matrix::ones(1, 3), &GF2Matrix::zeros(1, 2)]])
+            .unwrap();
+        assert_eq!(
+            m.elements,
+            vec![
+                vec![1, 0, 1, 0, 0],
+                vec![0, 1, 1, 0, 0],
+                vec![1, 1, 1, 0, 0]
+            ]
+        );
+
+        // A block column whose blocks have different numbers of columns.
+        let (o, po) = (GF2Matrix::ones(1, 2), PackedGF2Matrix::ones(1, 2));
+        let (w, pw) = (GF2Matrix::zeros(1, 3), PackedGF2Matrix::zeros(1, 3));
+        let err = MatrixError::DimensionMismatch {
+            operation: "block",
+            left: (2, 3),
+            right: (1, 2),
+        };
+        assert_eq!(GF2Matrix::block(&[[&a, &z], [&o, &w]]).unwrap_err(), err);
+        assert_eq!(
+            PackedGF2Matrix::block(&[[&pa, &pz], [&po, &pw]]).unwrap_err(),
+            err
+        );
+        // A block row whose blocks have different numbers of rows.
+        let err = MatrixError::DimensionMismatch {
+            operation: "block",
+            left: (2, 3),
+            right: (3, 2),
+        };
+        assert_eq!(GF2Matrix::block(&[[&a, &b]]).unwrap_err(), err);
+        assert_eq!(PackedGF2Matrix::block(&[[&pa, &pb]]).unwrap_err(), err);
+        assert_eq!(
+            pa.kron(&PackedGF2Matrix::identity(22)),
+            Err(MatrixError::TooManyColumns { ncols: 66, max: 64 })
+        );
+    }
+
+    #[test]
+    fn test_full_width_blocks() {
+        let i64 = PackedGF2Matrix::<u64>::identity(64);
+        assert_eq!(PackedGF2Matrix::zeros(64, 0).hstack(&i64).unwrap(), i64);
+        assert_eq!(PackedGF2Matrix::block(&[[&i64]]).unwrap(), i64);
+
+        let i128 = PackedGF2Matrix::<u128>::identity(128);
+        assert_eq!(i128.hstack(&PackedGF2Matrix::zeros(128, 0)).unwrap(), i128);
+        let half = PackedGF2Matrix::<u128>::identity(64);
+        let z = PackedGF2Matrix::zeros(64, 64);
+        assert_eq!(
+            PackedGF2Matrix::block(&[[&half, &z], [&z, &half]]).unwrap(),
+            i128
+        );
+    }
+}
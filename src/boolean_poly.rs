@@ -0,0 +1,453 @@
+use alloc::collections::BTreeSet;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, AddAssign, Mul};
+
+/// A monomial of the boolean polynomial ring `GF(2)[x_0, ..., x_63] / (x_i^2 - x_i)`.
+///
+/// Since `x_i^2 = x_i`, a monomial is a set of variables, stored as a bit mask:
+/// bit `i` is set when `x_i` divides the monomial. The empty set is the monomial
+/// `1`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Monomial(u64);
+
+/// Monomial orderings, with the variables ordered `x_0 > x_1 > ... > x_63`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MonomialOrder {
+    /// Lexicographic order.
+    Lex,
+    /// Degree, then lexicographic order.
+    DegLex,
+    /// Degree, then reverse lexicographic order (grevlex).
+    DegRevLex,
+}
+
+/// A polynomial of the boolean polynomial ring
+/// `GF(2)[x_0, ..., x_63] / (x_i^2 - x_i)`.
+///
+/// The field equations `x_i^2 = x_i` are built in, so polynomials are sums of
+/// square-free monomials and are evaluated at points of GF(2)^n.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BooleanPolynomial {
+    terms: BTreeSet<Monomial>,
+}
+
+impl Monomial {
+    /// Returns the monomial `1`.
+    pub fn one() -> Self {
+        Monomial(0)
+    }
+
+    /// Returns the monomial `x_i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= 64`.
+    pub fn variable(i: usize) -> Self {
+        assert!(i < 64, "boolean monomials support at most 64 variables");
+        Monomial(1 << i)
+    }
+
+    /// Returns the product of the given variables.
+    pub fn from_variables(variables: &[usize]) -> Self {
+        variables
+            .iter()
+            .fold(Self::one(), |acc, &i| acc.mul(Self::variable(i)))
+    }
+
+    /// Returns the monomial with the given bit mask of variables.
+    pub fn from_bits(bits: u64) -> Self {
+        Monomial(bits)
+    }
+
+    /// Returns the bit mask of the variables of the monomial.
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the indices of the variables of the monomial, in increasing order.
+    pub fn variables(&self) -> Vec<usize> {
+        (0..64).filter(|&i| (self.0 >> i) & 1 == 1).collect()
+    }
+
+    /// Returns the degree of the monomial.
+    pub fn degree(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns the product of two monomials.
+    #[allow(clippy::should_implement_trait)]
+    pub fn mul(self, other: Monomial) -> Monomial {
+        Monomial(self.0 | other.0)
+    }
+
+    /// Returns `true` if `self` divides `other`.
+    pub fn divides(&self, other: &Monomial) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// Returns the quotient `other / self`, or `None` if `self` does not divide
+    /// `other`.
+    ///
+    /// The quotient is the smallest monomial `q` with `q * self = other`.
+    pub fn divide(&self, other: &Monomial) -> Option<Monomial> {
+        self.divides(other).then_some(Monomial(other.0 & !self.0))
+    }
+
+    /// Returns the least common multiple of two monomials.
+    pub fn lcm(&self, other: &Monomial) -> Monomial {
+        Monomial(self.0 | other.0)
+    }
+
+    /// Evaluates the monomial at the point whose coordinates are the bits of
+    /// `point`.
+    pub fn evaluate(&self, point: u64) -> u8 {
+        (self.0 & !point == 0) as u8
+    }
+}
+
+impl fmt::Display for Monomial {
+    /// Formats the monomial as a product of variables, e.g. `x0*x3`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            return write!(f, "1");
+        }
+        let names: Vec<String> = self.variables().iter().map(|i| format!("x{}", i)).collect();
+        write!(f, "{}", names.join("*"))
+    }
+}
+
+impl MonomialOrder {
+    /// Compares two monomials in this order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::boolean_poly::{Monomial, MonomialOrder};
+    /// # use core::cmp::Ordering;
+    /// let a = Monomial::from_variables(&[0, 3]); // x0*x3
+    /// let b = Monomial::from_variables(&[1, 2]); // x1*x2
+    /// let c = Monomial::variable(0); // x0
+    ///
+    /// assert_eq!(MonomialOrder::Lex.compare(a, b), Ordering::Greater);
+    /// assert_eq!(MonomialOrder::DegRevLex.compare(a, b), Ordering::Less);
+    /// assert_eq!(MonomialOrder::Lex.compare(c, b), Ordering::Greater);
+    /// assert_eq!(MonomialOrder::DegLex.compare(c, b), Ordering::Less);
+    /// ```
+    pub fn compare(&self, a: Monomial, b: Monomial) -> Ordering {
+        let lex = || {
+            let diff = a.0 ^ b.0;
+            if diff == 0 {
+                Ordering::Equal
+            } else if a.0 & (diff & diff.wrapping_neg()) != 0 {
+                // a contains the largest variable on which they differ.
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            }
+        };
+        let revlex = || {
+            let diff = a.0 ^ b.0;
+            if diff == 0 {
+                Ordering::Equal
+            } else if a.0 & (1 << (63 - diff.leading_zeros())) != 0 {
+                // a contains the smallest variable on which they differ.
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        };
+        match self {
+            MonomialOrder::Lex => lex(),
+            MonomialOrder::DegLex => a.degree().cmp(&b.degree()).then_with(lex),
+            MonomialOrder::DegRevLex => a.degree().cmp(&b.degree()).then_with(revlex),
+        }
+    }
+
+    /// Sorts monomials in decreasing order.
+    pub fn sort_decreasing(&self, monomials: &mut [Monomial]) {
+        monomials.sort_by(|&a, &b| self.compare(b, a));
+    }
+}
+
+impl BooleanPolynomial {
+    /// Returns the zero polynomial.
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Returns the constant polynomial `1`.
+    pub fn one() -> Self {
+        Self::from_monomial(Monomial::one())
+    }
+
+    /// Returns the polynomial `x_i`.
+    pub fn variable(i: usize) -> Self {
+        Self::from_monomial(Monomial::variable(i))
+    }
+
+    /// Returns the polynomial consisting of a single monomial.
+    pub fn from_monomial(monomial: Monomial) -> Self {
+        Self {
+            terms: BTreeSet::from([monomial]),
+        }
+    }
+
+    /// Returns the sum of the given monomials; repeated monomials cancel out.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::boolean_poly::{BooleanPolynomial, Monomial};
+    /// // x0*x1 + x2 + 1
+    /// let p = BooleanPolynomial::from_monomials(&[
+    ///     Monomial::from_variables(&[0, 1]),
+    ///     Monomial::variable(2),
+    ///     Monomial::one(),
+    /// ]);
+    ///
+    /// assert_eq!(p.to_string(), "x0*x1 + x2 + 1");
+    /// assert_eq!(p.evaluate(&[1, 1, 0]), 0);
+    /// ```
+    pub fn from_monomials(monomials: &[Monomial]) -> Self {
+        let mut p = Self::zero();
+        for &m in monomials {
+            p.toggle(m);
+        }
+        p
+    }
+
+    /// Returns the monomials of the polynomial, in increasing bit-mask order.
+    pub fn monomials(&self) -> impl Iterator<Item = Monomial> + '_ {
+        self.terms.iter().copied()
+    }
+
+    /// Returns the monomials of the polynomial, in decreasing `order`.
+    pub fn sorted_monomials(&self, order: MonomialOrder) -> Vec<Monomial> {
+        let mut monomials: Vec<Monomial> = self.monomials().collect();
+        order.sort_decreasing(&mut monomials);
+        monomials
+    }
+
+    /// Returns the number of monomials of the polynomial.
+    pub fn weight(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Returns `true` if the polynomial is zero.
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Returns `true` if the polynomial contains the monomial `m`.
+    pub fn contains(&self, m: &Monomial) -> bool {
+        self.terms.contains(m)
+    }
+
+    /// Adds the monomial `m` to the polynomial.
+    pub fn toggle(&mut self, m: Monomial) {
+        if !self.terms.remove(&m) {
+            self.terms.insert(m);
+        }
+    }
+
+    /// Returns the degree of the polynomial, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.terms.iter().map(|m| m.degree()).max()
+    }
+
+    /// Returns the number of variables the polynomial may depend on, i.e. one
+    /// more than the largest variable index appearing in it.
+    pub fn nvars(&self) -> usize {
+        let all = self.terms.iter().fold(0u64, |acc, m| acc | m.0);
+        64 - all.leading_zeros() as usize
+    }
+
+    /// Returns the leading monomial in `order`, or `None` for the zero polynomial.
+    pub fn leading_monomial(&self, order: MonomialOrder) -> Option<Monomial> {
+        self.terms
+            .iter()
+            .copied()
+            .max_by(|&a, &b| order.compare(a, b))
+    }
+
+    /// Returns the product of the polynomial with a monomial.
+    pub fn mul_monomial(&self, m: Monomial) -> BooleanPolynomial {
+        let mut product = Self::zero();
+        for &t in &self.terms {
+            product.toggle(t.mul(m));
+        }
+        product
+    }
+
+    /// Returns the product of two polynomials.
+    pub fn mul(&self, other: &BooleanPolynomial) -> BooleanPolynomial {
+        let mut product = Self::zero();
+        for &a in &self.terms {
+            for &b in &other.terms {
+                product.toggle(a.mul(b));
+            }
+        }
+        product
+    }
+
+    /// Evaluates the polynomial at a point of GF(2)^n, given as bits `0` or `1`.
+    ///
+    /// Polynomials have at most 64 variables, so coordinates beyond the 64th
+    /// are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polynomial depends on a variable beyond `point.len()`.
+    pub fn evaluate(&self, point: &[u8]) -> u8 {
+        assert!(
+            self.nvars() <= point.len(),
+            "point has fewer coordinates than the polynomial has variables"
+        );
+        let bits = point
+            .iter()
+            .take(64)
+            .enumerate()
+            .fold(0u64, |acc, (i, &b)| acc | ((b as u64 & 1) << i));
+        self.evaluate_bits(bits)
+    }
+
+    /// Evaluates the polynomial at the point whose coordinates are the bits of
+    /// `point`.
+    pub fn evaluate_bits(&self, point: u64) -> u8 {
+        self.terms.iter().fold(0, |acc, m| acc ^ m.evaluate(point))
+    }
+}
+
+impl fmt::Display for BooleanPolynomial {
+    /// Formats the polynomial as a sum of monomials in decreasing degree
+    /// reverse lexicographic order, e.g. `x0*x1 + x2 + 1`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let terms: Vec<String> = self
+            .sorted_monomials(MonomialOrder::DegRevLex)
+            .iter()
+            .map(|m| m.to_string())
+            .collect();
+        write!(f, "{}", terms.join(" + "))
+    }
+}
+
+impl AddAssign<&BooleanPolynomial> for BooleanPolynomial {
+    fn add_assign(&mut self, rhs: &BooleanPolynomial) {
+        for &m in &rhs.terms {
+            self.toggle(m);
+        }
+    }
+}
+
+impl Add<&BooleanPolynomial> for &BooleanPolynomial {
+    type Output = BooleanPolynomial;
+
+    fn add(self, rhs: &BooleanPolynomial) -> BooleanPolynomial {
+        let mut sum = self.clone();
+        sum += rhs;
+        sum
+    }
+}
+
+impl Add for BooleanPolynomial {
+    type Output = BooleanPolynomial;
+
+    fn add(mut self, rhs: BooleanPolynomial) -> BooleanPolynomial {
+        self += &rhs;
+        self
+    }
+}
+
+impl Mul<&BooleanPolynomial> for &BooleanPolynomial {
+    type Output = BooleanPolynomial;
+
+    fn mul(self, rhs: &BooleanPolynomial) -> BooleanPolynomial {
+        BooleanPolynomial::mul(self, rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monomial_orders() {
+        let x = |vars: &[usize]| Monomial::from_variables(vars);
+        let mut monomials = vec![x(&[]), x(&[2]), x(&[0, 2]), x(&[1]), x(&[1, 2]), x(&[0])];
+
+        MonomialOrder::Lex.sort_decreasing(&mut monomials);
+        assert_eq!(
+            monomials,
+            vec![x(&[0, 2]), x(&[0]), x(&[1, 2]), x(&[1]), x(&[2]), x(&[])]
+        );
+
+        MonomialOrder::DegLex.sort_decreasing(&mut monomials);
+        assert_eq!(
+            monomials,
+            vec![x(&[0, 2]), x(&[1, 2]), x(&[0]), x(&[1]), x(&[2]), x(&[])]
+        );
+
+        // x0*x3 > x1*x2 in deglex, but grevlex penalises the smallest variable x3.
+        assert_eq!(
+            MonomialOrder::DegLex.compare(x(&[0, 3]), x(&[1, 2])),
+            Ordering::Greater
+        );
+        assert_eq!(
+            MonomialOrder::DegRevLex.compare(x(&[0, 3]), x(&[1, 2])),
+            Ordering::Less
+        );
+        assert_eq!(
+            MonomialOrder::DegRevLex.compare(x(&[0, 1, 4]), x(&[0, 2, 3])),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_and_evaluation() {
+        let x0 = BooleanPolynomial::variable(0);
+        let x1 = BooleanPolynomial::variable(1);
+        let one = BooleanPolynomial::one();
+
+        // (x0 + 1)(x0 + x1) = x0 + x0*x1 + x0 + x1 = x0*x1 + x1
+        let p = &(&x0 + &one) * &(&x0 + &x1);
+        assert_eq!(p.to_string(), "x0*x1 + x1");
+        assert_eq!(p.degree(), Some(2));
+        assert_eq!(p.nvars(), 2);
+
+        // x0 * x0 = x0 in the boolean ring.
+        assert_eq!(&x0 * &x0, x0);
+        assert!((&p + &p).is_zero());
+
+        for point in 0..4u64 {
+            let (a, b) = ((point & 1) as u8, ((point >> 1) & 1) as u8);
+            assert_eq!(p.evaluate_bits(point), (a + 1) % 2 * ((a + b) % 2));
+            assert_eq!(p.evaluate(&[a, b]), p.evaluate_bits(point));
+        }
+        // Coordinates beyond the 64th do not overflow the packed point.
+        let mut long = vec![1u8; 65];
+        long[1] = 0;
+        assert_eq!(p.evaluate(&long), 0);
+        long[0] = 0;
+        long[1] = 1;
+        assert_eq!(p.evaluate(&long), 1);
+
+        assert_eq!(
+            p.leading_monomial(MonomialOrder::Lex),
+            Some(Monomial::from_variables(&[0, 1]))
+        );
+        assert_eq!(
+            Monomial::variable(1).divide(&Monomial::from_variables(&[0, 1])),
+            Some(Monomial::variable(0))
+        );
+        assert_eq!(Monomial::variable(2).divide(&Monomial::variable(0)), None);
+    }
+}
@@ -0,0 +1,556 @@
+use crate::gf2_poly::Gf2Poly;
+use crate::matrix::Number;
+use crate::packed_gf2_matrix::PackedGF2Matrix;
+use crate::GF2Matrix;
+use alloc::vec::Vec;
+
+/// An `r x r` circulant matrix over GF(2), stored through its first row only.
+///
+/// Row `i` is the first row cyclically shifted `i` positions to the right, so
+/// entry `(i, j)` is `c_{(j - i) mod r}`. Identifying the first row with the
+/// polynomial `c(x) = c_0 + c_1 x + ... + c_{r-1} x^{r-1}`, circulant matrices
+/// of size `r` form the ring `GF(2)[x] / (x^r - 1)`:
+///
+/// - row `i` is `x^i c(x) mod (x^r - 1)`,
+/// - the product of two circulants is the product of their polynomials,
+/// - a row vector `u` times the matrix is `u(x) c(x) mod (x^r - 1)`.
+///
+/// This is the representation used by QC-MDPC codes such as BIKE, where `r`
+/// is in the tens of thousands and the dense matrix would not be practical.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CirculantMatrix {
+    first_row: Gf2Poly,
+    r: usize,
+}
+
+impl CirculantMatrix {
+    /// Creates a circulant matrix of size `r` from the polynomial of its first
+    /// row.
+    ///
+    /// The polynomial is reduced modulo `x^r - 1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r` is zero.
+    pub fn new(first_row: Gf2Poly, r: usize) -> Self {
+        assert!(r > 0, "circulant matrix size must be positive");
+        Self {
+            first_row: reduce_cyclic(&first_row, r),
+            r,
+        }
+    }
+
+    /// Creates a circulant matrix from the bits of its first row.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::circulant::CirculantMatrix;
+    /// let c = CirculantMatrix::from_first_row(&[1, 1, 0]);
+    ///
+    /// assert_eq!(c.to_gf2_matrix().elements, vec![vec![1, 1, 0], vec![0, 1, 1], vec![1, 0, 1]]);
+    /// ```
+    pub fn from_first_row(first_row: &[u8]) -> Self {
+        Self::new(Gf2Poly::from_coefficients(first_row), first_row.len())
+    }
+
+    /// Returns the `r x r` identity matrix as a circulant.
+    pub fn identity(r: usize) -> Self {
+        Self::new(Gf2Poly::one(), r)
+    }
+
+    /// Returns the `r x r` zero matrix as a circulant.
+    pub fn zero(r: usize) -> Self {
+        Self::new(Gf2Poly::zero(), r)
+    }
+
+    /// Returns the size `r` of the matrix.
+    pub fn size(&self) -> usize {
+        self.r
+    }
+
+    /// Returns the polynomial of the first row.
+    pub fn first_row(&self) -> &Gf2Poly {
+        &self.first_row
+    }
+
+    /// Returns the Hamming weight of each row.
+    pub fn row_weight(&self) -> usize {
+        self.first_row.weight()
+    }
+
+    /// Returns the polynomial of row `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= r`.
+    pub fn row(&self, i: usize) -> Gf2Poly {
+        assert!(i < self.r, "row index out of bounds");
+        self.mul_row_vector(&Gf2Poly::monomial(i))
+    }
+
+    /// Multiplies two circulant matrices of the same size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sizes differ.
+    pub fn mul(&self, other: &CirculantMatrix) -> CirculantMatrix {
+        assert_eq!(
+            self.r, other.r,
+            "circulant matrices must have the same size"
+        );
+        Self::new(self.first_row.mul(&other.first_row), self.r)
+    }
+
+    /// Adds two circulant matrices of the same size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sizes differ.
+    pub fn add(&self, other: &CirculantMatrix) -> CirculantMatrix {
+        assert_eq!(
+            self.r, other.r,
+            "circulant matrices must have the same size"
+        );
+        Self::new(&self.first_row + &other.first_row, self.r)
+    }
+
+    /// Multiplies the row vector `u` (as a polynomial of degree `< r`) by this
+    /// matrix, returning `u * C` as a polynomial.
+    pub fn mul_row_vector(&self, u: &Gf2Poly) -> Gf2Poly {
+        reduce_cyclic(&u.mul(&self.first_row), self.r)
+    }
+
+    /// Multiplies this matrix by the column vector `v` (as a polynomial of
+    /// degree `< r`), returning `C * v` as a polynomial.
+    ///
+    /// This equals `v * C^T`.
+    pub fn mul_column_vector(&self, v: &Gf2Poly) -> Gf2Poly {
+        self.transpose().mul_row_vector(v)
+    }
+
+    /// Returns the transpose of the matrix.
+    ///
+    /// The transpose is again circulant, with first row `c(x^{-1})`, i.e.
+    /// `c_0, c_{r-1}, ..., c_1`.
+    pub fn transpose(&self) -> CirculantMatrix {
+        let mut transposed = Gf2Poly::zero();
+        if let Some(d) = self.first_row.degree() {
+            for j in 0..=d {
+                if self.first_row.coefficient(j) == 1 {
+                    transposed.toggle_coefficient((self.r - j) % self.r);
+                }
+            }
+        }
+        Self::new(transposed, self.r)
+    }
+
+    /// Computes the inverse of the matrix.
+    ///
+    /// The inverse is the inverse of `c(x)` modulo `x^r - 1`, obtained with the
+    /// extended Euclidean algorithm.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the matrix is singular, i.e. `gcd(c(x), x^r - 1) != 1`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::circulant::CirculantMatrix;
+    /// let c = CirculantMatrix::from_first_row(&[1, 1, 1, 0, 0, 0, 0]);
+    /// let inverse = c.inverse().unwrap();
+    ///
+    /// assert_eq!(c.mul(&inverse), CirculantMatrix::identity(7));
+    /// ```
+    pub fn inverse(&self) -> Option<CirculantMatrix> {
+        let modulus = &Gf2Poly::monomial(self.r) + &Gf2Poly::one();
+        self.first_row
+            .inverse_mod(&modulus)
+            .map(|inverse| Self::new(inverse, self.r))
+    }
+
+    /// Expands the circulant into a bit-packed matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r` columns do not fit in `T`.
+    pub fn to_packed_matrix<T: Number>(&self) -> PackedGF2Matrix<T> {
+        assert!(
+            self.r <= core::mem::size_of::<T>() * 8,
+            "{} columns do not fit in the packed type",
+            self.r
+        );
+        let rows = (0..self.r).map(|i| self.row(i).to_packed(self.r)).collect();
+        PackedGF2Matrix::new(rows, self.r)
+    }
+
+    /// Expands the circulant into an explicit GF(2) matrix.
+    pub fn to_gf2_matrix(&self) -> GF2Matrix {
+        let elements = (0..self.r)
+            .map(|i| {
+                let row = self.row(i);
+                (0..self.r).map(|j| row.coefficient(j)).collect()
+            })
+            .collect();
+        GF2Matrix::new(elements)
+    }
+}
+
+/// A quasi-cyclic matrix over GF(2): a block matrix whose blocks are `r x r`
+/// circulant matrices.
+///
+/// Only the first row of every block is stored. Vectors acting on the matrix
+/// are split into blocks of length `r`, each given as a polynomial.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuasiCyclicMatrix {
+    blocks: Vec<Vec<CirculantMatrix>>,
+    r: usize,
+}
+
+impl QuasiCyclicMatrix {
+    /// Creates a quasi-cyclic matrix from its circulant blocks, given row by row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no blocks, if the block rows have different lengths,
+    /// or if the blocks do not all have the same size.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::circulant::{CirculantMatrix, QuasiCyclicMatrix};
+    /// // The parity-check matrix [H_0 | H_1] of a two-block QC code.
+    /// let h = QuasiCyclicMatrix::new(vec![vec![
+    ///     CirculantMatrix::from_first_row(&[1, 1, 0, 1, 0]),
+    ///     CirculantMatrix::from_first_row(&[1, 0, 0, 1, 1]),
+    /// ]]);
+    ///
+    /// assert_eq!((h.nrows(), h.ncols()), (5, 10));
+    /// ```
+    pub fn new(blocks: Vec<Vec<CirculantMatrix>>) -> Self {
+        assert!(
+            !blocks.is_empty() && !blocks[0].is_empty(),
+            "quasi-cyclic matrix needs at least one block"
+        );
+        let r = blocks[0][0].size();
+        let block_cols = blocks[0].len();
+        for block_row in &blocks {
+            assert_eq!(
+                block_row.len(),
+                block_cols,
+                "all block rows must have the same number of blocks"
+            );
+            for block in block_row {
+                assert_eq!(block.size(), r, "all blocks must have the same size");
+            }
+        }
+        Self { blocks, r }
+    }
+
+    /// Returns the size `r` of the circulant blocks.
+    pub fn block_size(&self) -> usize {
+        self.r
+    }
+
+    /// Returns the number of block rows.
+    pub fn block_rows(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns the number of block columns.
+    pub fn block_cols(&self) -> usize {
+        self.blocks[0].len()
+    }
+
+    /// Returns the number of rows of the expanded matrix.
+    pub fn nrows(&self) -> usize {
+        self.block_rows() * self.r
+    }
+
+    /// Returns the number of columns of the expanded matrix.
+    pub fn ncols(&self) -> usize {
+        self.block_cols() * self.r
+    }
+
+    /// Returns the circulant block at block position `(i, j)`.
+    pub fn block(&self, i: usize, j: usize) -> &CirculantMatrix {
+        &self.blocks[i][j]
+    }
+
+    /// Multiplies two quasi-cyclic matrices with the same block size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the block sizes differ or the block dimensions do not match.
+    pub fn mul(&self, other: &QuasiCyclicMatrix) -> QuasiCyclicMatrix {
+        assert_eq!(self.r, other.r, "block sizes must match");
+        assert_eq!(
+            self.block_cols(),
+            other.block_rows(),
+            "left-hand side block columns must match right-hand side block rows"
+        );
+
+        let blocks = (0..self.block_rows())
+            .map(|i| {
+                (0..other.block_cols())
+                    .map(|j| {
+                        let mut acc = Gf2Poly::zero();
+                        for k in 0..self.block_cols() {
+                            acc += &self.blocks[i][k]
+                                .first_row
+                                .mul(&other.blocks[k][j].first_row);
+                        }
+                        CirculantMatrix::new(acc, self.r)
+                    })
+                    .collect()
+            })
+            .collect();
+        QuasiCyclicMatrix::new(blocks)
+    }
+
+    /// Multiplies the row vector `u`, split into `block_rows` polynomials, by
+    /// this matrix.
+    ///
+    /// # Returns
+    ///
+    /// The product `u * M`, split into `block_cols` polynomials.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `u` does not have one polynomial per block row.
+    pub fn mul_row_vector(&self, u: &[Gf2Poly]) -> Vec<Gf2Poly> {
+        assert_eq!(
+            u.len(),
+            self.block_rows(),
+            "vector must have one block per block row"
+        );
+        (0..self.block_cols())
+            .map(|j| {
+                let mut acc = Gf2Poly::zero();
+                for (i, u_i) in u.iter().enumerate() {
+                    acc += &u_i.mul(&self.blocks[i][j].first_row);
+                }
+                reduce_cyclic(&acc, self.r)
+            })
+            .collect()
+    }
+
+    /// Multiplies this matrix by the column vector `v`, split into `block_cols`
+    /// polynomials, e.g. to compute the syndrome `H * e` of an error vector.
+    ///
+    /// # Returns
+    ///
+    /// The product `M * v`, split into `block_rows` polynomials.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` does not have one polynomial per block column.
+    pub fn mul_column_vector(&self, v: &[Gf2Poly]) -> Vec<Gf2Poly> {
+        self.transpose().mul_row_vector(v)
+    }
+
+    /// Returns the transpose of the matrix, which transposes both the block
+    /// layout and every block.
+    pub fn transpose(&self) -> QuasiCyclicMatrix {
+        let blocks = (0..self.block_cols())
+            .map(|j| {
+                (0..self.block_rows())
+                    .map(|i| self.blocks[i][j].transpose())
+                    .collect()
+            })
+            .collect();
+        QuasiCyclicMatrix::new(blocks)
+    }
+
+    /// Expands the matrix into a bit-packed matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of columns does not fit in `T`.
+    pub fn to_packed_matrix<T: Number>(&self) -> PackedGF2Matrix<T> {
+        let n = self.ncols();
+        assert!(
+            n <= core::mem::size_of::<T>() * 8,
+            "{} columns do not fit in the packed type",
+            n
+        );
+        let expanded = self.to_gf2_matrix();
+        let rows = expanded
+            .elements
+            .iter()
+            .map(|row| {
+                row.iter().fold(T::zero(), |acc, &bit| {
+                    if bit == 1 {
+                        (acc << 1) ^ T::one()
+                    } else {
+                        acc << 1
+                    }
+                })
+            })
+            .collect();
+        PackedGF2Matrix::new(rows, n)
+    }
+
+    /// Expands the matrix into an explicit GF(2) matrix.
+    pub fn to_gf2_matrix(&self) -> GF2Matrix {
+        let mut elements = Vec::with_capacity(self.nrows());
+        for block_row in &self.blocks {
+            let expanded: Vec<GF2Matrix> = block_row.iter().map(|b| b.to_gf2_matrix()).collect();
+            for i in 0..self.r {
+                elements.push(
+                    expanded
+                        .iter()
+                        .flat_map(|b| b.elements[i].iter().copied())
+                        .collect(),
+                );
+            }
+        }
+        GF2Matrix::new(elements)
+    }
+}
+
+/// Reduces a polynomial modulo `x^r - 1` by folding the coefficients of degree
+/// `>= r` back onto the low part.
+fn reduce_cyclic(p: &Gf2Poly, r: usize) -> Gf2Poly {
+    let mut result = p.clone();
+    while result.degree().is_some_and(|d| d >= r) {
+        let (low, high) = result.split_at(r);
+        result = &low + &high;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dense_mul(a: &GF2Matrix, b: &GF2Matrix) -> Vec<Vec<u8>> {
+        a.elements
+            .iter()
+            .map(|row| {
+                (0..b.elements[0].len())
+                    .map(|j| {
+                        row.iter()
+                            .enumerate()
+                            .fold(0, |acc, (k, &x)| acc ^ (x & b.elements[k][j]))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_circulant_expansion_and_transpose() {
+        let c = CirculantMatrix::from_first_row(&[1, 0, 1, 1, 0]);
+        let dense = c.to_gf2_matrix();
+        assert_eq!(
+            dense.elements,
+            vec![
+                vec![1, 0, 1, 1, 0],
+                vec![0, 1, 0, 1, 1],
+                vec![1, 0, 1, 0, 1],
+                vec![1, 1, 0, 1, 0],
+                vec![0, 1, 1, 0, 1],
+            ]
+        );
+        let transposed = c.transpose().to_gf2_matrix();
+        for i in 0..5 {
+            for j in 0..5 {
+                assert_eq!(transposed.elements[i][j], dense.elements[j][i]);
+            }
+        }
+        assert_eq!(
+            c.to_packed_matrix::<u8>(),
+            PackedGF2Matrix::new(vec![0b10110, 0b01011, 0b10101, 0b11010, 0b01101], 5)
+        );
+    }
+
+    #[test]
+    fn test_circulant_arithmetic() {
+        let a = CirculantMatrix::from_first_row(&[1, 1, 0, 1, 0, 0, 1]);
+        let b = CirculantMatrix::from_first_row(&[0, 1, 1, 0, 0, 0, 1]);
+        assert_eq!(
+            a.mul(&b).to_gf2_matrix().elements,
+            dense_mul(&a.to_gf2_matrix(), &b.to_gf2_matrix())
+        );
+
+        let u = Gf2Poly::from_coefficients(&[1, 0, 0, 1, 1]);
+        let u_dense = GF2Matrix::new(vec![(0..7).map(|i| u.coefficient(i)).collect()]);
+        let product = dense_mul(&u_dense, &a.to_gf2_matrix());
+        assert_eq!(
+            (0..7)
+                .map(|i| a.mul_row_vector(&u).coefficient(i))
+                .collect::<Vec<_>>(),
+            product[0]
+        );
+        let column = a.mul_column_vector(&u);
+        let a_dense = a.to_gf2_matrix();
+        for i in 0..7 {
+            let expected = (0..7).fold(0, |acc, j| {
+                acc ^ (a_dense.elements[i][j] & u.coefficient(j))
+            });
+            assert_eq!(column.coefficient(i), expected);
+        }
+    }
+
+    #[test]
+    fn test_circulant_inverse() {
+        // x^r - 1 = (x + 1)(...), so even-weight circulants are singular.
+        assert_eq!(
+            CirculantMatrix::from_first_row(&[1, 1, 0, 0, 0]).inverse(),
+            None
+        );
+
+        let r = 509;
+        let mut first_row = Gf2Poly::zero();
+        for i in [0, 3, 17, 64, 130, 255, 400] {
+            first_row.toggle_coefficient(i);
+        }
+        let c = CirculantMatrix::new(first_row, r);
+        let inverse = c.inverse().unwrap();
+        assert_eq!(c.mul(&inverse), CirculantMatrix::identity(r));
+        assert_eq!(inverse.mul(&c), CirculantMatrix::identity(r));
+    }
+
+    #[test]
+    fn test_quasi_cyclic_matrix() {
+        let h0 = CirculantMatrix::from_first_row(&[1, 1, 0, 1, 0]);
+        let h1 = CirculantMatrix::from_first_row(&[1, 0, 0, 1, 1]);
+        let h2 = CirculantMatrix::from_first_row(&[0, 1, 1, 1, 0]);
+        let h = QuasiCyclicMatrix::new(vec![vec![h0.clone(), h1.clone()], vec![h2.clone(), h0]]);
+        let g = QuasiCyclicMatrix::new(vec![vec![h1], vec![h2]]);
+
+        let dense_h = h.to_gf2_matrix();
+        assert_eq!(
+            (dense_h.elements.len(), dense_h.elements[0].len()),
+            (10, 10)
+        );
+        assert_eq!(
+            h.mul(&g).to_gf2_matrix().elements,
+            dense_mul(&dense_h, &g.to_gf2_matrix())
+        );
+
+        let transposed = h.transpose().to_gf2_matrix();
+        for i in 0..10 {
+            for j in 0..10 {
+                assert_eq!(transposed.elements[i][j], dense_h.elements[j][i]);
+            }
+        }
+
+        // Syndrome of an error vector e = (e_0 | e_1).
+        let e = vec![
+            Gf2Poly::from_coefficients(&[0, 1]),
+            Gf2Poly::from_coefficients(&[0, 0, 0, 1]),
+        ];
+        let syndrome = h.mul_column_vector(&e);
+        let packed = h.to_packed_matrix::<u16>();
+        let packed_syndrome = packed.matrix_by_vector(&0b01000_00010);
+        let expected: Vec<u8> = syndrome
+            .iter()
+            .flat_map(|s| (0..5).map(|i| s.coefficient(i)).collect::<Vec<_>>())
+            .collect();
+        let expected = expected.iter().fold(0u16, |acc, &b| (acc << 1) | b as u16);
+        assert_eq!(packed_syndrome, expected);
+    }
+}
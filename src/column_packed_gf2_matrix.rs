@@ -0,0 +1,263 @@
+use alloc::{vec, vec::Vec};
+
+use crate::matrix::Number;
+use crate::packed_gf2_matrix::PackedGF2Matrix;
+use crate::view::GF2Storage;
+
+/// Returns the number of bits of `T`.
+fn width<T: Number>() -> usize {
+    core::mem::size_of::<T>() * 8
+}
+
+/// Returns the masks used by [`transpose_block`], one per level: the mask of
+/// level `j` keeps the low `j` bits of every group of `2j` bits.
+fn block_masks<T: Number>() -> Vec<T> {
+    let mut masks = Vec::new();
+    let mut j = width::<T>() / 2;
+    let mut mask = (0..j).fold(T::zero(), |mask, bit| mask ^ (T::one() << bit));
+    while j != 0 {
+        masks.push(mask);
+        j /= 2;
+        mask = mask ^ (mask << j);
+    }
+    masks
+}
+
+/// Transposes in place the square bit matrix whose row `i` is `block[i]`,
+/// column 0 being the most significant bit.
+///
+/// The matrix is split into four quadrants whose off-diagonal pair is swapped,
+/// recursively, which takes `log2(w)` passes of `w / 2` word operations for
+/// words of `w` bits.
+fn transpose_block<T: Number>(block: &mut [T], masks: &[T]) {
+    let w = block.len();
+    let mut j = w / 2;
+    for &mask in masks {
+        let mut k = 0;
+        while k < w {
+            let t = (block[k] ^ (block[k + j] >> j)) & mask;
+            block[k] = block[k] ^ t;
+            block[k + j] = block[k + j] ^ (t << j);
+            k = (k + j + 1) & !j;
+        }
+        j /= 2;
+    }
+}
+
+/// Transposes rows of `ncols` bits, `w` rows at a time.
+///
+/// Returns, column after column, the `ceil(rows.len() / w)` words holding the
+/// entries of that column, row 0 being the most significant bit of the first
+/// word.
+pub(crate) fn transpose_rows<T: Number>(rows: &[T], ncols: usize) -> Vec<T> {
+    let w = width::<T>();
+    assert!(
+        ncols <= w,
+        "{} columns do not fit in the packed type",
+        ncols
+    );
+    if ncols == 0 {
+        return Vec::new();
+    }
+
+    let masks = block_masks::<T>();
+    let blocks = rows.len().div_ceil(w);
+    let mut words = vec![T::zero(); ncols * blocks];
+    let mut block = vec![T::zero(); w];
+
+    for (b, chunk) in rows.chunks(w).enumerate() {
+        // Align column 0 on the most significant bit and pad with zero rows.
+        for (i, slot) in block.iter_mut().enumerate() {
+            *slot = chunk.get(i).map_or(T::zero(), |&row| row << (w - ncols));
+        }
+        transpose_block(&mut block, &masks);
+        for col in 0..ncols {
+            words[col * blocks + b] = block[col];
+        }
+    }
+
+    words
+}
+
+/// A bit-packed GF(2) matrix stored column by column.
+///
+/// Each column is stored in `ceil(nrows / w)` words of `w` bits, row `i` being
+/// bit `w - 1 - i % w` of word `i / w`. Unlike [`PackedGF2Matrix`], whose
+/// columns are bits of every row, a column is a contiguous slice, and the
+/// number of rows is not limited by the width of `T`.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::column_packed_gf2_matrix::ColumnPackedGF2Matrix;
+/// # use lin_algebra::packed_gf2_matrix::PackedGF2Matrix;
+/// let m = PackedGF2Matrix::new(vec![0b110u8, 0b011, 0b101], 3);
+/// let columns = ColumnPackedGF2Matrix::from(&m);
+///
+/// assert_eq!(columns.column(0), &[0b1010_0000]);
+/// assert_eq!(columns.to_packed(), m);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnPackedGF2Matrix<T: Number> {
+    words: Vec<T>,
+    nrows: usize,
+    ncols: usize,
+}
+
+impl<T: Number> ColumnPackedGF2Matrix<T> {
+    /// Creates a matrix from its columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - One entry per column, holding `ceil(nrows / w)` words
+    ///   packed as described on [`ColumnPackedGF2Matrix`].
+    /// * `nrows` - The number of rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a column does not have `ceil(nrows / w)` words.
+    pub fn new(columns: Vec<Vec<T>>, nrows: usize) -> Self {
+        let blocks = nrows.div_ceil(width::<T>());
+        assert!(
+            columns.iter().all(|column| column.len() == blocks),
+            "every column must have {} words",
+            blocks
+        );
+        Self {
+            ncols: columns.len(),
+            words: columns.concat(),
+            nrows,
+        }
+    }
+
+    /// Returns the number of rows in the matrix.
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    /// Returns the number of columns in the matrix.
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    fn blocks(&self) -> usize {
+        self.nrows.div_ceil(width::<T>())
+    }
+
+    /// Returns the words holding column `col`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `col` is out of bounds.
+    pub fn column(&self, col: usize) -> &[T] {
+        assert!(
+            col < self.ncols,
+            "column index out of bounds: index is {}, but matrix has {} columns",
+            col,
+            self.ncols
+        );
+        let blocks = self.blocks();
+        &self.words[col * blocks..(col + 1) * blocks]
+    }
+
+    /// Converts to the row-major [`PackedGF2Matrix`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the columns do not fit in `T`.
+    pub fn to_packed(&self) -> PackedGF2Matrix<T> {
+        let w = width::<T>();
+        assert!(
+            self.ncols <= w,
+            "{} columns do not fit in the packed type",
+            self.ncols
+        );
+        if self.ncols == 0 {
+            return PackedGF2Matrix::new(vec![T::zero(); self.nrows], 0);
+        }
+
+        let masks = block_masks::<T>();
+        let blocks = self.blocks();
+        let mut rows = Vec::with_capacity(self.nrows);
+        let mut block = vec![T::zero(); w];
+
+        for b in 0..blocks {
+            for (col, slot) in block.iter_mut().enumerate() {
+                *slot = if col < self.ncols {
+                    self.words[col * blocks + b]
+                } else {
+                    T::zero()
+                };
+            }
+            transpose_block(&mut block, &masks);
+            let in_block = (self.nrows - b * w).min(w);
+            rows.extend(block[..in_block].iter().map(|&row| row >> (w - self.ncols)));
+        }
+
+        PackedGF2Matrix::new(rows, self.ncols)
+    }
+}
+
+impl<T: Number> From<&PackedGF2Matrix<T>> for ColumnPackedGF2Matrix<T> {
+    fn from(matrix: &PackedGF2Matrix<T>) -> Self {
+        let rows: Vec<T> = (0..matrix.nrows()).map(|row| matrix.row(row)).collect();
+        Self {
+            words: transpose_rows(&rows, matrix.ncols()),
+            nrows: matrix.nrows(),
+            ncols: matrix.ncols(),
+        }
+    }
+}
+
+impl<T: Number> From<PackedGF2Matrix<T>> for ColumnPackedGF2Matrix<T> {
+    fn from(matrix: PackedGF2Matrix<T>) -> Self {
+        Self::from(&matrix)
+    }
+}
+
+impl<T: Number> GF2Storage for ColumnPackedGF2Matrix<T> {
+    fn shape(&self) -> (usize, usize) {
+        (self.nrows, self.ncols)
+    }
+
+    fn get(&self, row: usize, col: usize) -> u8 {
+        let w = width::<T>();
+        let word = self.column(col)[row / w];
+        ((word >> (w - 1 - row % w)) & T::one() != T::zero()) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_rng::xorshift64;
+    use crate::view::MatrixView;
+    use crate::GF2Matrix;
+
+    #[test]
+    fn test_block_transpose_matches_definition() {
+        let mut state = 0x853c_49e6_748f_ea9bu64;
+        let mut next = || xorshift64(&mut state);
+
+        let rows: Vec<u16> = (0..16).map(|_| next() as u16).collect();
+        let mut block = rows.clone();
+        transpose_block(&mut block, &block_masks::<u16>());
+        for (i, &column) in block.iter().enumerate() {
+            for (j, &row) in rows.iter().enumerate() {
+                assert_eq!((column >> (15 - j)) & 1, (row >> (15 - i)) & 1);
+            }
+        }
+
+        // Several row blocks, a partial last block and fewer columns than bits.
+        let m = PackedGF2Matrix::new((0..150).map(|_| next() >> 27).collect::<Vec<u64>>(), 37);
+        let columns = ColumnPackedGF2Matrix::from(&m);
+        assert_eq!(columns.column(36).len(), 3);
+        assert_eq!(
+            MatrixView::new(&columns, 0..150, 0..37)
+                .to_gf2_matrix()
+                .elements,
+            GF2Matrix::from(&m).elements
+        );
+        assert_eq!(columns.to_packed(), m);
+    }
+}
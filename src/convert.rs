@@ -1,6 +1,8 @@
 use crate::gf2_matrix::GF2Matrix;
 use crate::matrix::{Matrix, Number};
 use crate::packed_gf2_matrix::{BitOrder, PackedGF2Matrix};
+use crate::sparse_gf2_matrix::SparseGF2Matrix;
+use alloc::{vec, vec::Vec};
 
 impl<T: Number> From<&PackedGF2Matrix<T>> for GF2Matrix {
     fn from(int_matrix: &PackedGF2Matrix<T>) -> Self {
@@ -14,50 +16,42 @@ impl<T: Number> From<PackedGF2Matrix<T>> for GF2Matrix {
     }
 }
 
-impl<T: Number> Matrix<T>
-where
-    T: Number + From<usize>,
-{
+impl<T: Number> Matrix<T> {
+    /// Packs a row of bits into a word, the first bit being the most
+    /// significant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an entry is not `0` or `1`, or if the row does not fit in `T`.
     pub fn from_vec_to_int_msb(v: &[u8]) -> T {
-        let mut x = 0usize;
         let n = v.len();
+        assert!(
+            n <= core::mem::size_of::<T>() * 8,
+            "{} columns do not fit in the packed type",
+            n
+        );
+        let mut x = T::zero();
 
         for (i, &bit) in v.iter().enumerate() {
             assert!(bit == 0 || bit == 1);
             if bit == 1 {
-                x |= 1 << (n - 1 - i);
+                x = x ^ (T::one() << (n - 1 - i));
             }
         }
 
-        T::from(x)
+        x
     }
 }
 
-impl<T: Number> From<GF2Matrix> for PackedGF2Matrix<T>
-where
-    T: Number + From<usize>,
-{
+impl<T: Number> From<GF2Matrix> for PackedGF2Matrix<T> {
     fn from(gf2_matrix: GF2Matrix) -> Self {
-        let ve: Vec<T> = gf2_matrix
-            .elements
-            .iter()
-            .map(|row| Matrix::<T>::from_vec_to_int_msb(&row))
-            .collect();
-        Self::from_vec(ve)
+        Self::from(&gf2_matrix)
     }
 }
 
-impl<T: Number> From<&GF2Matrix> for PackedGF2Matrix<T>
-where
-    T: Number + From<usize>,
-{
+impl<T: Number> From<&GF2Matrix> for PackedGF2Matrix<T> {
     fn from(gf2_matrix: &GF2Matrix) -> Self {
-        let ve: Vec<T> = gf2_matrix
-            .elements
-            .iter()
-            .map(|row| Matrix::<T>::from_vec_to_int_msb(&row))
-            .collect();
-        Self::from_vec(ve)
+        gf2_matrix.view().to_packed()
     }
 }
 
@@ -73,6 +67,68 @@ impl<T: Number> From<&Vec<T>> for PackedGF2Matrix<T> {
     }
 }
 
+impl From<&GF2Matrix> for SparseGF2Matrix {
+    fn from(gf2_matrix: &GF2Matrix) -> Self {
+        let ncols = gf2_matrix.elements.first().map_or(0, |row| row.len());
+        let rows = gf2_matrix
+            .elements
+            .iter()
+            .map(|row| (0..ncols).filter(|&j| row[j] == 1).collect())
+            .collect();
+        SparseGF2Matrix::from_rows(ncols, rows)
+    }
+}
+
+impl From<&SparseGF2Matrix> for GF2Matrix {
+    fn from(sparse: &SparseGF2Matrix) -> Self {
+        let elements = (0..sparse.nrows())
+            .map(|i| {
+                let mut row = vec![0u8; sparse.ncols()];
+                for &j in sparse.row(i) {
+                    row[j] = 1;
+                }
+                row
+            })
+            .collect();
+        GF2Matrix::new(elements)
+    }
+}
+
+impl<T: Number> From<&PackedGF2Matrix<T>> for SparseGF2Matrix {
+    fn from(packed: &PackedGF2Matrix<T>) -> Self {
+        let ncols = packed.ncols();
+        let rows = (0..packed.nrows())
+            .map(|i| {
+                let row = packed.row(i);
+                (0..ncols)
+                    .filter(|&j| (row >> (ncols - 1 - j)) & T::one() != T::zero())
+                    .collect()
+            })
+            .collect();
+        SparseGF2Matrix::from_rows(ncols, rows)
+    }
+}
+
+impl<T: Number> From<&SparseGF2Matrix> for PackedGF2Matrix<T> {
+    fn from(sparse: &SparseGF2Matrix) -> Self {
+        let ncols = sparse.ncols();
+        assert!(
+            ncols <= core::mem::size_of::<T>() * 8,
+            "{} columns do not fit in the packed type",
+            ncols
+        );
+        let rows = (0..sparse.nrows())
+            .map(|i| {
+                sparse
+                    .row(i)
+                    .iter()
+                    .fold(T::zero(), |acc, &j| acc ^ (T::one() << (ncols - 1 - j)))
+            })
+            .collect();
+        PackedGF2Matrix::new(rows, ncols)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -115,4 +171,17 @@ mod tests {
         let gf2_matrix: GF2Matrix = (&int_matrix).into();
         assert_eq!(gf2_matrix.elements, expected);
     }
+
+    #[test]
+    fn test_from_vec_to_int_msb_for_every_width() {
+        assert_eq!(Matrix::<u8>::from_vec_to_int_msb(&[1, 0, 1, 1]), 0b1011);
+        let mut bits = vec![0u8; 128];
+        bits[0] = 1;
+        bits[127] = 1;
+        assert_eq!(
+            Matrix::<u128>::from_vec_to_int_msb(&bits),
+            (1u128 << 127) | 1
+        );
+        assert_eq!(Matrix::<u64>::from_vec_to_int_msb(&bits[64..]), 1);
+    }
 }
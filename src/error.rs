@@ -0,0 +1,47 @@
+use core::fmt;
+
+/// Errors reported by matrix constructions whose operands can have
+/// incompatible shapes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatrixError {
+    /// The shapes `(rows, columns)` of two operands are incompatible.
+    DimensionMismatch {
+        /// Name of the operation that failed.
+        operation: &'static str,
+        /// Shape of the left operand.
+        left: (usize, usize),
+        /// Shape of the right operand.
+        right: (usize, usize),
+    },
+    /// The result has more columns than fit in the packed word type.
+    TooManyColumns {
+        /// Number of columns of the result.
+        ncols: usize,
+        /// Number of bits of the packed word type.
+        max: usize,
+    },
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::DimensionMismatch {
+                operation,
+                left,
+                right,
+            } => write!(
+                f,
+                "{}: incompatible shapes {}x{} and {}x{}",
+                operation, left.0, left.1, right.0, right.1
+            ),
+            MatrixError::TooManyColumns { ncols, max } => write!(
+                f,
+                "{} columns do not fit in a packed word of {} bits",
+                ncols, max
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MatrixError {}
@@ -1,4 +1,9 @@
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
+
 use crate::matrix::{Matrix, MatrixCommon, MatrixTrait};
+use crate::simd;
+use crate::view::{GF2Storage, GF2StorageMut};
 
 ///
 /// Implements the trait MatrixTrait: needs to implement
@@ -114,9 +119,7 @@ impl MatrixTrait<u8> for GF2Matrix {
             }
             for i in 0..rows {
                 if i != r && m_copy.elements[i][lead] == 1 {
-                    for j in 0..cols {
-                        m_copy.elements[i][j] = (m_copy.elements[i][j] + m_copy.elements[r][j]) % 2;
-                    }
+                    m_copy.add_row(i, r);
                     operations.push((i, r));
                 }
             }
@@ -153,6 +156,18 @@ impl MatrixTrait<u8> for GF2Matrix {
 }
 
 impl GF2Matrix {
+    /// Adds row `source` to row `target`.
+    fn add_row(&mut self, target: usize, source: usize) {
+        let (target_row, source_row) = if target < source {
+            let (head, tail) = self.elements.split_at_mut(source);
+            (&mut head[target], &tail[0])
+        } else {
+            let (head, tail) = self.elements.split_at_mut(target);
+            (&mut tail[0], &head[source])
+        };
+        simd::xor_bytes_into(target_row, source_row);
+    }
+
     fn apply_operations(operations: &Vec<(usize, usize)>, v: &Vec<u8>) -> Vec<u8> {
         let mut result = v.clone();
         for &(op1, op2) in operations.iter() {
@@ -200,7 +215,7 @@ impl GF2Matrix {
         GF2Matrix::new(elements)
     }
 
-    /// Solves for X such that equation A*x = b where A  is a GF2Matrix and b a Vec<u8>.
+    /// Solves for X such that equation A*x = b where A  is a GF2Matrix and b a `Vec<u8>`.
     ///
     /// # Arguments
     ///
@@ -228,12 +243,13 @@ impl GF2Matrix {
 
     fn rank_echelon_form(&self) -> usize {
         let mut count = 0;
-        let mut pivot_columns = std::collections::HashSet::new();
+        let mut is_pivot_column = vec![false; self.ncols()];
 
         for i in 0..self.nrows() {
             let p = GF2Matrix::get_pivot(&self.elements[i]);
             if let Some(col) = p {
-                if pivot_columns.insert(col) {
+                if !is_pivot_column[col] {
+                    is_pivot_column[col] = true;
                     count += 1;
                 }
             }
@@ -245,14 +261,15 @@ impl GF2Matrix {
         let rows = self.nrows();
         let cols = self.ncols();
 
-        let mut pivots = std::collections::HashMap::new();
+        // (pivot column, pivot row), in increasing column order.
+        let mut pivots: Vec<(usize, usize)> = Vec::new();
         let mut kernel_base: Vec<Vec<u8>> = Vec::new();
         let mut free_columns: Vec<usize> = Vec::new();
         let mut row_index = 0;
 
         for j in 0..cols {
             if row_index < rows && self.elements[row_index][j] == 1 {
-                pivots.insert(j, row_index);
+                pivots.push((j, row_index));
                 row_index += 1;
             } else {
                 free_columns.push(j);
@@ -263,7 +280,7 @@ impl GF2Matrix {
             let mut kernel_vector = vec![0; cols];
             kernel_vector[free_col] = 1;
 
-            for (&p_index, &p_row) in &pivots {
+            for &(p_index, p_row) in &pivots {
                 let mut sum = 0;
 
                 for col in (0..cols).rev() {
@@ -281,3 +298,33 @@ impl GF2Matrix {
         kernel_base
     }
 }
+
+impl GF2Storage for GF2Matrix {
+    fn shape(&self) -> (usize, usize) {
+        (MatrixCommon::nrows(self), MatrixCommon::ncols(self))
+    }
+
+    fn get(&self, row: usize, col: usize) -> u8 {
+        self.elements[row][col]
+    }
+}
+
+impl GF2StorageMut for GF2Matrix {
+    fn set(&mut self, row: usize, col: usize, value: u8) {
+        self.elements[row][col] = value;
+    }
+
+    fn swap_row_segments(&mut self, a: usize, b: usize, cols: Range<usize>) {
+        for col in cols {
+            let tmp = self.elements[a][col];
+            self.elements[a][col] = self.elements[b][col];
+            self.elements[b][col] = tmp;
+        }
+    }
+
+    fn add_row_segment(&mut self, target: usize, source: usize, cols: Range<usize>) {
+        for col in cols {
+            self.elements[target][col] ^= self.elements[source][col];
+        }
+    }
+}
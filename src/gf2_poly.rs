@@ -0,0 +1,1211 @@
+use crate::circulant::CirculantMatrix;
+use crate::matrix::Number;
+use crate::packed_gf2_matrix::PackedGF2Matrix;
+use crate::simd;
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, Mul, Rem};
+
+/// A polynomial over GF(2), stored in bit-packed form.
+///
+/// Bit `i` of the packed words is the coefficient of `x^i`, so the
+/// coefficients are laid out in `u64` words from the constant term upwards.
+/// The representation is kept normalized: there are never trailing zero
+/// words, which makes the zero polynomial an empty word vector and lets
+/// equality be a plain comparison of the words.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Gf2Poly {
+    words: Vec<u64>,
+}
+
+impl Gf2Poly {
+    /// Returns the zero polynomial.
+    pub fn zero() -> Self {
+        Self { words: Vec::new() }
+    }
+
+    /// Returns the constant polynomial `1`.
+    pub fn one() -> Self {
+        Self::monomial(0)
+    }
+
+    /// Returns the monomial `x^degree`.
+    pub fn monomial(degree: usize) -> Self {
+        let mut p = Self::zero();
+        p.toggle_coefficient(degree);
+        p
+    }
+
+    /// Creates a polynomial from its coefficients, constant term first.
+    ///
+    /// # Arguments
+    ///
+    /// * `coefficients` - coefficient of `x^i` at index `i`, each `0` or `1`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_poly::Gf2Poly;
+    /// // 1 + x + x^3
+    /// let p = Gf2Poly::from_coefficients(&[1, 1, 0, 1]);
+    /// assert_eq!(p.degree(), Some(3));
+    /// ```
+    pub fn from_coefficients(coefficients: &[u8]) -> Self {
+        let mut p = Self::zero();
+        for (i, &c) in coefficients.iter().enumerate() {
+            assert!(c == 0 || c == 1, "coefficients must be 0 or 1");
+            if c == 1 {
+                p.toggle_coefficient(i);
+            }
+        }
+        p
+    }
+
+    /// Creates a polynomial from packed `u64` words, where bit `i` of the words
+    /// is the coefficient of `x^i`.
+    ///
+    /// Trailing zero words are allowed and removed.
+    pub fn from_words(words: Vec<u64>) -> Self {
+        let mut p = Self { words };
+        p.normalize();
+        p
+    }
+
+    /// Returns the packed `u64` words of the polynomial, constant term in the
+    /// least significant bit of the first word.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Creates a polynomial from a packed vector of length `len`.
+    ///
+    /// The vector uses the MSB order of [`PackedGF2Matrix`]: column `i`, i.e.
+    /// bit `len - 1 - i`, holds the coefficient of `x^i`.
+    pub fn from_packed<T: Number>(value: T, len: usize) -> Self {
+        let mut p = Self::zero();
+        for i in 0..len {
+            if (value >> (len - 1 - i)) & T::one() != T::zero() {
+                p.toggle_coefficient(i);
+            }
+        }
+        p
+    }
+
+    /// Packs the polynomial into a vector of length `len`, using the column
+    /// convention of [`Self::from_packed`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the degree of the polynomial is not smaller than `len`.
+    pub fn to_packed<T: Number>(&self, len: usize) -> T {
+        assert!(
+            self.degree().is_none_or(|d| d < len),
+            "polynomial does not fit in {} coefficients",
+            len
+        );
+        let mut value = T::zero();
+        for i in 0..len {
+            if self.coefficient(i) == 1 {
+                value = value ^ (T::one() << (len - 1 - i));
+            }
+        }
+        value
+    }
+
+    /// Returns the coefficients of the polynomial, constant term first.
+    ///
+    /// The returned vector has `degree + 1` entries, and is empty for the zero
+    /// polynomial.
+    pub fn coefficients(&self) -> Vec<u8> {
+        match self.degree() {
+            None => Vec::new(),
+            Some(d) => (0..=d).map(|i| self.coefficient(i)).collect(),
+        }
+    }
+
+    /// Returns the degree of the polynomial, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        let last = *self.words.last()?;
+        Some((self.words.len() - 1) * 64 + 63 - last.leading_zeros() as usize)
+    }
+
+    /// Returns `true` if this is the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns the coefficient of `x^idx`.
+    pub fn coefficient(&self, idx: usize) -> u8 {
+        match self.words.get(idx / 64) {
+            Some(&w) => ((w >> (idx % 64)) & 1) as u8,
+            None => 0,
+        }
+    }
+
+    /// Flips the coefficient of `x^idx`.
+    pub fn toggle_coefficient(&mut self, idx: usize) {
+        let word = idx / 64;
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] ^= 1 << (idx % 64);
+        self.normalize();
+    }
+
+    /// Returns the number of nonzero coefficients.
+    pub fn weight(&self) -> usize {
+        simd::popcount(&self.words) as usize
+    }
+
+    /// Splits the polynomial at `x^k`, returning `(low, high)` such that
+    /// `self = low + x^k * high` and `deg(low) < k`.
+    pub(crate) fn split_at(&self, k: usize) -> (Gf2Poly, Gf2Poly) {
+        let word = k / 64;
+        let bit = k % 64;
+        if word >= self.words.len() {
+            return (self.clone(), Gf2Poly::zero());
+        }
+
+        let mut low_words = self.words[..=word].to_vec();
+        low_words[word] &= (1u64 << bit).wrapping_sub(1);
+
+        let mut high_words = Vec::with_capacity(self.words.len() - word);
+        for i in word..self.words.len() {
+            let mut w = self.words[i] >> bit;
+            if bit != 0 && i + 1 < self.words.len() {
+                w |= self.words[i + 1] << (64 - bit);
+            }
+            high_words.push(w);
+        }
+
+        (
+            Gf2Poly::from_words(low_words),
+            Gf2Poly::from_words(high_words),
+        )
+    }
+
+    /// Adds `x^shift * other` to this polynomial in place.
+    pub(crate) fn add_shifted(&mut self, other: &Gf2Poly, shift: usize) {
+        if other.is_zero() {
+            return;
+        }
+        let word_shift = shift / 64;
+        let bit_shift = shift % 64;
+        let needed = other.words.len() + word_shift + 1;
+        if self.words.len() < needed {
+            self.words.resize(needed, 0);
+        }
+        if bit_shift == 0 {
+            let end = word_shift + other.words.len();
+            simd::xor_into(&mut self.words[word_shift..end], &other.words);
+        } else {
+            for (i, &w) in other.words.iter().enumerate() {
+                self.words[i + word_shift] ^= w << bit_shift;
+                self.words[i + word_shift + 1] ^= w >> (64 - bit_shift);
+            }
+        }
+        self.normalize();
+    }
+
+    /// Multiplies two polynomials (carry-less multiplication).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_poly::Gf2Poly;
+    /// // (1 + x)(1 + x) = 1 + x^2 over GF(2)
+    /// let p = Gf2Poly::from_coefficients(&[1, 1]);
+    /// assert_eq!(p.mul(&p).coefficients(), vec![1, 0, 1]);
+    /// ```
+    pub fn mul(&self, other: &Gf2Poly) -> Gf2Poly {
+        if self.is_zero() || other.is_zero() {
+            return Gf2Poly::zero();
+        }
+        let mut words = vec![0u64; self.words.len() + other.words.len()];
+        for (i, &a) in self.words.iter().enumerate() {
+            if a == 0 {
+                continue;
+            }
+            for (j, &b) in other.words.iter().enumerate() {
+                let (lo, hi) = clmul_word(a, b);
+                words[i + j] ^= lo;
+                words[i + j + 1] ^= hi;
+            }
+        }
+        let mut result = Gf2Poly { words };
+        result.normalize();
+        result
+    }
+
+    /// Returns the remainder of the division of this polynomial by `modulus`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is the zero polynomial.
+    pub fn rem(&self, modulus: &Gf2Poly) -> Gf2Poly {
+        let m_deg = modulus.degree().expect("division by the zero polynomial");
+        let mut r = self.clone();
+        while let Some(r_deg) = r.degree() {
+            if r_deg < m_deg {
+                break;
+            }
+            r.add_shifted(modulus, r_deg - m_deg);
+        }
+        r
+    }
+
+    /// Returns `self * other mod modulus`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is the zero polynomial.
+    pub fn mul_mod(&self, other: &Gf2Poly, modulus: &Gf2Poly) -> Gf2Poly {
+        self.mul(other).rem(modulus)
+    }
+
+    /// Returns `self^exponent mod modulus`, computed by square-and-multiply.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is the zero polynomial.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_poly::Gf2Poly;
+    /// // x has order 7 modulo the primitive polynomial 1 + x + x^3.
+    /// let modulus = Gf2Poly::from_coefficients(&[1, 1, 0, 1]);
+    /// assert_eq!(Gf2Poly::monomial(1).pow_mod(7, &modulus), Gf2Poly::one());
+    /// ```
+    pub fn pow_mod(&self, exponent: u128, modulus: &Gf2Poly) -> Gf2Poly {
+        let mut result = Gf2Poly::one().rem(modulus);
+        let mut base = self.rem(modulus);
+        let mut e = exponent;
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result.mul_mod(&base, modulus);
+            }
+            e >>= 1;
+            if e > 0 {
+                base = base.mul_mod(&base, modulus);
+            }
+        }
+        result
+    }
+
+    /// Divides this polynomial by `divisor`, returning `(quotient, remainder)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is the zero polynomial.
+    pub fn div_rem(&self, divisor: &Gf2Poly) -> (Gf2Poly, Gf2Poly) {
+        let d_deg = divisor.degree().expect("division by the zero polynomial");
+        let mut quotient = Gf2Poly::zero();
+        let mut r = self.clone();
+        while let Some(r_deg) = r.degree() {
+            if r_deg < d_deg {
+                break;
+            }
+            quotient.toggle_coefficient(r_deg - d_deg);
+            r.add_shifted(divisor, r_deg - d_deg);
+        }
+        (quotient, r)
+    }
+
+    /// Returns the greatest common divisor of two polynomials.
+    ///
+    /// The gcd of two zero polynomials is the zero polynomial.
+    pub fn gcd(&self, other: &Gf2Poly) -> Gf2Poly {
+        let mut a = self.clone();
+        let mut b = other.clone();
+        while !b.is_zero() {
+            let r = a.rem(&b);
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    /// Returns the least common multiple of two polynomials.
+    ///
+    /// The lcm is zero if either polynomial is zero.
+    pub fn lcm(&self, other: &Gf2Poly) -> Gf2Poly {
+        if self.is_zero() || other.is_zero() {
+            return Gf2Poly::zero();
+        }
+        self.div_rem(&self.gcd(other)).0.mul(other)
+    }
+
+    /// Runs the extended Euclidean algorithm.
+    ///
+    /// # Returns
+    ///
+    /// A triple `(g, s, t)` with `g = gcd(self, other)` and
+    /// `s * self + t * other = g`.
+    pub fn extended_gcd(&self, other: &Gf2Poly) -> (Gf2Poly, Gf2Poly, Gf2Poly) {
+        let (mut r0, mut r1) = (self.clone(), other.clone());
+        let (mut s0, mut s1) = (Gf2Poly::one(), Gf2Poly::zero());
+        let (mut t0, mut t1) = (Gf2Poly::zero(), Gf2Poly::one());
+        while !r1.is_zero() {
+            let (q, r) = r0.div_rem(&r1);
+            r0 = core::mem::replace(&mut r1, r);
+            let s = &s0 + &q.mul(&s1);
+            s0 = core::mem::replace(&mut s1, s);
+            let t = &t0 + &q.mul(&t1);
+            t0 = core::mem::replace(&mut t1, t);
+        }
+        (r0, s0, t0)
+    }
+
+    /// Returns the inverse of this polynomial modulo `modulus`.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the polynomial is not coprime to `modulus`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_poly::Gf2Poly;
+    /// let modulus = Gf2Poly::from_coefficients(&[1, 1, 0, 1]);
+    /// let a = Gf2Poly::from_coefficients(&[0, 1, 1]);
+    /// let inverse = a.inverse_mod(&modulus).unwrap();
+    ///
+    /// assert_eq!(a.mul_mod(&inverse, &modulus), Gf2Poly::one());
+    /// ```
+    pub fn inverse_mod(&self, modulus: &Gf2Poly) -> Option<Gf2Poly> {
+        let (g, s, _) = self.rem(modulus).extended_gcd(modulus);
+        if g == Gf2Poly::one() {
+            Some(s.rem(modulus))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the formal derivative of the polynomial.
+    ///
+    /// Over GF(2) only the odd-degree terms survive: `(x^i)' = x^(i-1)` for odd
+    /// `i` and `0` for even `i`.
+    pub fn derivative(&self) -> Gf2Poly {
+        let mut result = Gf2Poly::zero();
+        if let Some(d) = self.degree() {
+            for i in (1..=d).step_by(2) {
+                if self.coefficient(i) == 1 {
+                    result.toggle_coefficient(i - 1);
+                }
+            }
+        }
+        result
+    }
+
+    /// Factors the polynomial into irreducible polynomials over GF(2).
+    ///
+    /// The factorization runs square-free factorization, distinct-degree
+    /// factorization and a deterministic trace-based equal-degree splitting.
+    ///
+    /// # Returns
+    ///
+    /// The distinct irreducible factors together with their multiplicities,
+    /// sorted by degree. Constant polynomials have no factors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polynomial is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_poly::Gf2Poly;
+    /// // x^4 + x^3 + x + 1 = (x + 1)^2 (x^2 + x + 1)
+    /// let p = Gf2Poly::from_coefficients(&[1, 1, 0, 1, 1]);
+    ///
+    /// assert_eq!(
+    ///     p.factor(),
+    ///     vec![
+    ///         (Gf2Poly::from_coefficients(&[1, 1]), 2),
+    ///         (Gf2Poly::from_coefficients(&[1, 1, 1]), 1),
+    ///     ]
+    /// );
+    /// ```
+    pub fn factor(&self) -> Vec<(Gf2Poly, usize)> {
+        assert!(!self.is_zero(), "cannot factor the zero polynomial");
+
+        let mut factors = Vec::new();
+        for (square_free, multiplicity) in self.square_free_factorization() {
+            for (product, degree) in square_free.distinct_degree_factorization() {
+                for irreducible in product.equal_degree_factorization(degree) {
+                    factors.push((irreducible, multiplicity));
+                }
+            }
+        }
+        factors.sort_by(|(a, _), (b, _)| a.cmp_by_degree(b));
+        factors
+    }
+
+    /// Tests whether the polynomial is irreducible over GF(2) with Rabin's test.
+    ///
+    /// A polynomial `f` of degree `n >= 1` is irreducible if and only if
+    /// `x^(2^n) = x mod f` and `gcd(x^(2^(n/q)) - x, f) = 1` for every prime `q`
+    /// dividing `n`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_poly::Gf2Poly;
+    /// assert!(Gf2Poly::from_coefficients(&[1, 1, 0, 1]).is_irreducible());
+    /// assert!(!Gf2Poly::from_coefficients(&[1, 0, 1]).is_irreducible());
+    /// ```
+    pub fn is_irreducible(&self) -> bool {
+        let n = match self.degree() {
+            None | Some(0) => return false,
+            Some(n) => n,
+        };
+        let x = Gf2Poly::monomial(1);
+        // x^(2^k) mod f by repeated squaring.
+        let frobenius = |k: usize| {
+            let mut h = x.rem(self);
+            for _ in 0..k {
+                h = h.mul_mod(&h, self);
+            }
+            h
+        };
+
+        if frobenius(n) != x.rem(self) {
+            return false;
+        }
+        prime_factors(n as u128).into_iter().all(|q| {
+            let h = &frobenius(n / q as usize) + &x;
+            self.gcd(&h) == Gf2Poly::one()
+        })
+    }
+
+    /// Tests whether the polynomial is primitive, i.e. irreducible of degree `n`
+    /// with order `2^n - 1`.
+    ///
+    /// The connection polynomial of a maximum-length LFSR is primitive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polynomial is irreducible of degree above 128, since its
+    /// order is computed in a `u128`.
+    pub fn is_primitive(&self) -> bool {
+        if !self.is_irreducible() || self.coefficient(0) == 0 {
+            return false;
+        }
+        let n = self.degree().unwrap();
+        assert!(
+            n <= 128,
+            "primitivity of polynomials of degree above 128 is not supported"
+        );
+        self.order() == Some(u128::MAX >> (128 - n))
+    }
+
+    /// Returns the order of the polynomial, i.e. the least `e >= 1` such that
+    /// it divides `x^e - 1`.
+    ///
+    /// For an irreducible polynomial this is the multiplicative order of any of
+    /// its roots, and it equals `2^degree - 1` exactly when the polynomial is
+    /// primitive.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the constant term is zero (then `x` divides the polynomial and
+    /// no such `e` exists), if the polynomial is zero, or if the order cannot
+    /// be computed in a `u128`: it does not fit, or an irreducible factor has
+    /// degree above 128.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_poly::Gf2Poly;
+    /// // 1 + x + x^4 is primitive, 1 + x + x^2 + x^3 + x^4 is not.
+    /// assert_eq!(Gf2Poly::from_coefficients(&[1, 1, 0, 0, 1]).order(), Some(15));
+    /// assert_eq!(Gf2Poly::from_coefficients(&[1, 1, 1, 1, 1]).order(), Some(5));
+    /// ```
+    pub fn order(&self) -> Option<u128> {
+        if self.coefficient(0) == 0 {
+            return None;
+        }
+        if self.degree() == Some(0) {
+            return Some(1);
+        }
+
+        let x = Gf2Poly::monomial(1);
+        let mut order: u128 = 1;
+        let mut max_multiplicity = 1;
+        for (f, multiplicity) in self.factor() {
+            let d = f.degree().unwrap();
+            if d > 128 {
+                return None;
+            }
+            // The order of an irreducible polynomial of degree d divides 2^d - 1.
+            let group_order = u128::MAX >> (128 - d);
+            let mut f_order = group_order;
+            for q in prime_factors(group_order) {
+                while f_order.is_multiple_of(q) && x.pow_mod(f_order / q, &f) == Gf2Poly::one() {
+                    f_order /= q;
+                }
+            }
+            order = (order / gcd_u128(order, f_order)).checked_mul(f_order)?;
+            max_multiplicity = max_multiplicity.max(multiplicity);
+        }
+
+        // Repeated factors multiply the order by the least power of two that is
+        // at least the largest multiplicity.
+        order.checked_mul(max_multiplicity.next_power_of_two() as u128)
+    }
+
+    /// Returns the matrix of the linear map `b -> self * b mod modulus` on
+    /// polynomials of degree smaller than `n = deg(modulus)`.
+    ///
+    /// Polynomials are packed as vectors of length `n` with the convention of
+    /// [`Self::to_packed`], so that
+    ///
+    /// `M * b.to_packed(n) = self.mul_mod(&b, modulus).to_packed(n)`.
+    ///
+    /// Column `j` of the matrix holds `self * x^j mod modulus`; each column is
+    /// the previous one multiplied by `x`, so the matrix is Toeplitz-like. For
+    /// `modulus = x^n - 1` it is the transpose of [`Self::circulant_matrix`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` has degree zero, or if its degree does not fit in `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_poly::Gf2Poly;
+    /// let modulus = Gf2Poly::from_coefficients(&[1, 1, 0, 1]);
+    /// let a = Gf2Poly::from_coefficients(&[0, 1, 1]);
+    /// let b = Gf2Poly::from_coefficients(&[1, 0, 1]);
+    /// let m = a.multiplication_matrix::<u8>(&modulus);
+    ///
+    /// assert_eq!(
+    ///     m.matrix_by_vector(&b.to_packed(3)),
+    ///     a.mul_mod(&b, &modulus).to_packed(3)
+    /// );
+    /// ```
+    pub fn multiplication_matrix<T: Number>(&self, modulus: &Gf2Poly) -> PackedGF2Matrix<T> {
+        let n = modulus.degree().expect("modulus must be nonzero");
+        assert!(n > 0, "modulus must have positive degree");
+        assert!(
+            n <= core::mem::size_of::<T>() * 8,
+            "{} columns do not fit in the packed type",
+            n
+        );
+
+        let mut rows = vec![T::zero(); n];
+        let mut column = self.rem(modulus);
+        for j in 0..n {
+            for (i, row) in rows.iter_mut().enumerate() {
+                if column.coefficient(i) == 1 {
+                    *row = *row ^ (T::one() << (n - 1 - j));
+                }
+            }
+            column = column.mul_mod(&Gf2Poly::monomial(1), modulus);
+        }
+        PackedGF2Matrix::new(rows, n)
+    }
+
+    /// Returns the `r x r` circulant matrix whose first row is this polynomial
+    /// modulo `x^r - 1`, expanded as by [`CirculantMatrix`].
+    ///
+    /// Row `i` holds `x^i * self mod (x^r - 1)`, so a row vector `u` times the
+    /// matrix is `u * self mod (x^r - 1)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r` is zero, or if `r` columns do not fit in `T`.
+    pub fn circulant_matrix<T: Number>(&self, r: usize) -> PackedGF2Matrix<T> {
+        CirculantMatrix::new(self.clone(), r).to_packed_matrix()
+    }
+
+    /// Recovers the polynomial `a mod modulus` from the matrix of multiplication
+    /// by `a` modulo `modulus`.
+    ///
+    /// This is the inverse of [`Self::multiplication_matrix`]: the polynomial is
+    /// read from column `0`, which is the image of the constant `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square of size `deg(modulus)`, or if it is not
+    /// the multiplication matrix of any polynomial.
+    pub fn from_multiplication_matrix<T: Number>(
+        matrix: &PackedGF2Matrix<T>,
+        modulus: &Gf2Poly,
+    ) -> Gf2Poly {
+        let n = modulus.degree().expect("modulus must be nonzero");
+        assert!(
+            matrix.nrows() == n && matrix.ncols() == n,
+            "matrix must be {} x {}",
+            n,
+            n
+        );
+        let a = Gf2Poly::from_packed(matrix.column_packed(0), n);
+        assert!(
+            &a.multiplication_matrix::<T>(modulus) == matrix,
+            "matrix is not a multiplication matrix modulo the given polynomial"
+        );
+        a
+    }
+
+    /// Splits the polynomial into pairwise coprime square-free parts, returned
+    /// with the multiplicity of their irreducible factors.
+    fn square_free_factorization(&self) -> Vec<(Gf2Poly, usize)> {
+        let mut result = Vec::new();
+        let mut c = self.gcd(&self.derivative());
+        let mut w = self.div_rem(&c).0;
+        let mut multiplicity = 1;
+
+        while w.degree() != Some(0) {
+            let y = w.gcd(&c);
+            let factor = w.div_rem(&y).0;
+            if factor.degree() != Some(0) {
+                result.push((factor, multiplicity));
+            }
+            w = y;
+            c = c.div_rem(&w).0;
+            multiplicity += 1;
+        }
+
+        // What is left is a perfect square.
+        if c.degree() != Some(0) {
+            for (factor, m) in c.square_root().square_free_factorization() {
+                result.push((factor, 2 * m));
+            }
+        }
+        result
+    }
+
+    /// Returns the square root of a polynomial having only even-degree terms.
+    fn square_root(&self) -> Gf2Poly {
+        let mut result = Gf2Poly::zero();
+        if let Some(d) = self.degree() {
+            for i in (0..=d).step_by(2) {
+                if self.coefficient(i) == 1 {
+                    result.toggle_coefficient(i / 2);
+                }
+            }
+        }
+        result
+    }
+
+    /// Splits a square-free polynomial into products of irreducible factors of
+    /// equal degree, returned as `(product, degree)` pairs.
+    fn distinct_degree_factorization(&self) -> Vec<(Gf2Poly, usize)> {
+        let mut result = Vec::new();
+        let mut remaining = self.clone();
+        let x = Gf2Poly::monomial(1);
+        // h = x^(2^i) mod remaining
+        let mut h = x.rem(&remaining);
+        let mut degree = 1;
+
+        while remaining.degree().unwrap() >= 2 * degree {
+            h = h.mul_mod(&h, &remaining);
+            let mut h_minus_x = h.clone();
+            h_minus_x.add_shifted(&x, 0);
+            let g = remaining.gcd(&h_minus_x);
+            if g.degree() != Some(0) {
+                remaining = remaining.div_rem(&g).0;
+                h = h.rem(&remaining);
+                result.push((g, degree));
+            }
+            degree += 1;
+        }
+
+        if let Some(d) = remaining.degree() {
+            if d > 0 {
+                result.push((remaining, d));
+            }
+        }
+        result
+    }
+
+    /// Splits a product of distinct irreducible polynomials of degree `degree`
+    /// into its factors.
+    ///
+    /// For each factor `f_i`, the trace map `a + a^2 + ... + a^(2^(degree-1))`
+    /// reduces to `0` or `1` modulo `f_i`, so its gcd with the product separates
+    /// the factors. Running through `a = x, x^2, ...` always finds a splitting
+    /// element, which keeps the factorization deterministic.
+    fn equal_degree_factorization(&self, degree: usize) -> Vec<Gf2Poly> {
+        let n = self.degree().unwrap();
+        if n == degree {
+            return vec![self.clone()];
+        }
+
+        for k in 1..n {
+            let a = Gf2Poly::monomial(k).rem(self);
+            let mut trace = a.clone();
+            let mut power = a;
+            for _ in 1..degree {
+                power = power.mul_mod(&power, self);
+                trace.add_shifted(&power, 0);
+            }
+
+            let g = self.gcd(&trace);
+            let g_deg = g.degree().unwrap_or(n);
+            if g_deg > 0 && g_deg < n {
+                let h = self.div_rem(&g).0;
+                let mut factors = g.equal_degree_factorization(degree);
+                factors.extend(h.equal_degree_factorization(degree));
+                return factors;
+            }
+        }
+        unreachable!("equal-degree factorization found no splitting element")
+    }
+
+    fn cmp_by_degree(&self, other: &Gf2Poly) -> core::cmp::Ordering {
+        self.degree()
+            .cmp(&other.degree())
+            .then_with(|| self.words.iter().rev().cmp(other.words.iter().rev()))
+    }
+
+    fn normalize(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+}
+
+impl fmt::Display for Gf2Poly {
+    /// Formats the polynomial as a sum of powers of `x`, highest degree first,
+    /// e.g. `x^3 + x + 1`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let degree = match self.degree() {
+            None => return write!(f, "0"),
+            Some(d) => d,
+        };
+        let mut first = true;
+        for i in (0..=degree).rev() {
+            if self.coefficient(i) == 0 {
+                continue;
+            }
+            if !first {
+                write!(f, " + ")?;
+            }
+            first = false;
+            match i {
+                0 => write!(f, "1")?,
+                1 => write!(f, "x")?,
+                _ => write!(f, "x^{}", i)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AddAssign<&Gf2Poly> for Gf2Poly {
+    fn add_assign(&mut self, rhs: &Gf2Poly) {
+        self.add_shifted(rhs, 0);
+    }
+}
+
+impl Add<&Gf2Poly> for &Gf2Poly {
+    type Output = Gf2Poly;
+
+    fn add(self, rhs: &Gf2Poly) -> Gf2Poly {
+        let mut result = self.clone();
+        result += rhs;
+        result
+    }
+}
+
+impl Add for Gf2Poly {
+    type Output = Gf2Poly;
+
+    fn add(mut self, rhs: Gf2Poly) -> Gf2Poly {
+        self += &rhs;
+        self
+    }
+}
+
+impl Mul<&Gf2Poly> for &Gf2Poly {
+    type Output = Gf2Poly;
+
+    fn mul(self, rhs: &Gf2Poly) -> Gf2Poly {
+        Gf2Poly::mul(self, rhs)
+    }
+}
+
+impl Div<&Gf2Poly> for &Gf2Poly {
+    type Output = Gf2Poly;
+
+    /// Returns the quotient of the division, see [`Gf2Poly::div_rem`].
+    fn div(self, rhs: &Gf2Poly) -> Gf2Poly {
+        self.div_rem(rhs).0
+    }
+}
+
+impl Rem<&Gf2Poly> for &Gf2Poly {
+    type Output = Gf2Poly;
+
+    fn rem(self, rhs: &Gf2Poly) -> Gf2Poly {
+        Gf2Poly::rem(self, rhs)
+    }
+}
+
+/// Carry-less product of two 64-bit words, returned as `(low, high)` words.
+fn clmul_word(a: u64, b: u64) -> (u64, u64) {
+    let mut lo = 0u64;
+    let mut hi = 0u64;
+    let mut bits = a;
+    while bits != 0 {
+        let i = bits.trailing_zeros();
+        lo ^= b << i;
+        if i != 0 {
+            hi ^= b >> (64 - i);
+        }
+        bits &= bits - 1;
+    }
+    (lo, hi)
+}
+
+fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// Returns `a * b mod m` without overflowing `u128`.
+fn mul_mod_u128(a: u128, b: u128, m: u128) -> u128 {
+    if m <= u64::MAX as u128 {
+        return (a % m) * (b % m) % m;
+    }
+    let mut result = 0u128;
+    let mut a = a % m;
+    let mut b = b % m;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod_u128(result, a, m);
+        }
+        a = add_mod_u128(a, a, m);
+        b >>= 1;
+    }
+    result
+}
+
+fn add_mod_u128(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+fn pow_mod_u128(mut base: u128, mut exponent: u128, m: u128) -> u128 {
+    let mut result = 1 % m;
+    base %= m;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod_u128(result, base, m);
+        }
+        base = mul_mod_u128(base, base, m);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Miller–Rabin test with the first 20 primes as bases, which is deterministic
+/// far beyond `2^64` and has a negligible error probability up to `2^128`.
+fn is_prime_u128(n: u128) -> bool {
+    const BASES: [u128; 20] = [
+        2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71,
+    ];
+    if n < 2 {
+        return false;
+    }
+    for &p in BASES.iter() {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    'bases: for &a in BASES.iter() {
+        let mut x = pow_mod_u128(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod_u128(x, x, n);
+            if x == n - 1 {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// Finds a non-trivial factor of the odd composite `n` with Pollard's rho
+/// method (Brent's cycle detection).
+fn pollard_rho(n: u128) -> u128 {
+    for c in 1.. {
+        let f = |x: u128| add_mod_u128(mul_mod_u128(x, x, n), c, n);
+        let mut y = 2u128;
+        let mut x;
+        let mut factor = 1;
+        let mut cycle = 1;
+        while factor == 1 {
+            x = y;
+            for _ in 0..cycle {
+                y = f(y);
+                factor = gcd_u128(x.abs_diff(y), n);
+                if factor != 1 {
+                    break;
+                }
+            }
+            cycle *= 2;
+        }
+        if factor != n {
+            return factor;
+        }
+    }
+    unreachable!()
+}
+
+/// Returns the distinct prime factors of `n`, in increasing order.
+fn prime_factors(mut n: u128) -> Vec<u128> {
+    let mut primes = Vec::new();
+    let mut p = 2;
+    while p < 1000 && p * p <= n {
+        if n.is_multiple_of(p) {
+            primes.push(p);
+            while n.is_multiple_of(p) {
+                n /= p;
+            }
+        }
+        p += 1;
+    }
+
+    let mut stack = vec![n];
+    while let Some(m) = stack.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime_u128(m) {
+            primes.push(m);
+        } else {
+            let d = pollard_rho(m);
+            stack.push(d);
+            stack.push(m / d);
+        }
+    }
+
+    primes.sort_unstable();
+    primes.dedup();
+    primes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GF2Matrix;
+
+    #[test]
+    fn test_degree_and_coefficients() {
+        assert_eq!(Gf2Poly::zero().degree(), None);
+        assert_eq!(Gf2Poly::one().degree(), Some(0));
+        assert_eq!(Gf2Poly::monomial(130).degree(), Some(130));
+
+        let p = Gf2Poly::from_coefficients(&[1, 0, 1, 1, 0, 0]);
+        assert_eq!(p.degree(), Some(3));
+        assert_eq!(p.coefficients(), vec![1, 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_add_shifted() {
+        let mut p = Gf2Poly::from_coefficients(&[1, 1]);
+        p.add_shifted(&Gf2Poly::from_coefficients(&[1, 1]), 1);
+        assert_eq!(p.coefficients(), vec![1, 0, 1]);
+
+        let mut q = Gf2Poly::monomial(70);
+        q.add_shifted(&Gf2Poly::monomial(7), 63);
+        assert!(q.is_zero());
+    }
+
+    #[test]
+    fn test_mul_and_rem() {
+        let a = Gf2Poly::from_coefficients(&[1, 0, 1, 1]);
+        let b = Gf2Poly::from_coefficients(&[1, 1]);
+        assert_eq!(a.mul(&b).coefficients(), vec![1, 1, 1, 0, 1]);
+        assert_eq!(a.mul(&b).rem(&a), Gf2Poly::zero());
+        assert_eq!(a.rem(&b), Gf2Poly::one());
+
+        // Products spanning several words.
+        let p = Gf2Poly::monomial(100).mul(&Gf2Poly::from_coefficients(&[1, 1]));
+        assert_eq!(p.degree(), Some(101));
+        assert_eq!(p.coefficient(100), 1);
+    }
+
+    #[test]
+    fn test_pow_mod() {
+        // 1 + x^2 + x^5 is primitive: x has order 31.
+        let modulus = Gf2Poly::from_coefficients(&[1, 0, 1, 0, 0, 1]);
+        let x = Gf2Poly::monomial(1);
+        assert_eq!(x.pow_mod(31, &modulus), Gf2Poly::one());
+        assert_ne!(x.pow_mod(1, &modulus), Gf2Poly::one());
+        assert_eq!(x.pow_mod(0, &modulus), Gf2Poly::one());
+        assert_eq!(
+            x.pow_mod(1 << 40, &modulus),
+            x.pow_mod((1 << 40) % 31, &modulus)
+        );
+    }
+
+    #[test]
+    fn test_gcd_lcm_div_rem() {
+        let a = Gf2Poly::from_coefficients(&[1, 1, 0, 1]);
+        let b = Gf2Poly::from_coefficients(&[1, 1, 1]);
+        let c = Gf2Poly::from_coefficients(&[1, 1]);
+        assert_eq!(a.mul(&c).gcd(&b.mul(&c)), c);
+        assert_eq!(a.mul(&c).lcm(&b.mul(&c)), a.mul(&b).mul(&c));
+
+        let (q, r) = a.mul(&b).div_rem(&b);
+        assert_eq!(q, a);
+        assert!(r.is_zero());
+    }
+
+    #[test]
+    fn test_factor() {
+        let f1 = Gf2Poly::from_coefficients(&[1, 1]);
+        let f2 = Gf2Poly::from_coefficients(&[1, 1, 1]);
+        let f3 = Gf2Poly::from_coefficients(&[1, 1, 0, 1]);
+        let f4 = Gf2Poly::from_coefficients(&[1, 0, 1, 1]);
+        let x = Gf2Poly::monomial(1);
+
+        // x^2 (x+1)^4 (x^2+x+1) (x^3+x+1)^3 (x^3+x^2+1)
+        let mut p = x.mul(&x);
+        for _ in 0..4 {
+            p = p.mul(&f1);
+        }
+        p = p.mul(&f2).mul(&f3).mul(&f3).mul(&f3).mul(&f4);
+
+        assert_eq!(p.factor(), vec![(x, 2), (f1, 4), (f2, 1), (f3, 3), (f4, 1)]);
+        assert_eq!(Gf2Poly::one().factor(), vec![]);
+    }
+
+    #[test]
+    fn test_order() {
+        assert_eq!(Gf2Poly::from_coefficients(&[0, 1]).order(), None);
+        assert_eq!(Gf2Poly::one().order(), Some(1));
+        // (x + 1)^3 divides x^4 - 1 but not x^2 - 1.
+        let p = Gf2Poly::from_coefficients(&[1, 1, 1, 1]);
+        assert_eq!(p.order(), Some(4));
+        // 1 + x^3 + x^31 is primitive.
+        let mut primitive = Gf2Poly::from_coefficients(&[1, 0, 0, 1]);
+        primitive.toggle_coefficient(31);
+        assert_eq!(primitive.order(), Some((1 << 31) - 1));
+        // 1 + x + x^3 + x^4 + x^64 is primitive.
+        let mut primitive = Gf2Poly::from_coefficients(&[1, 1, 0, 1, 1]);
+        primitive.toggle_coefficient(64);
+        assert_eq!(primitive.order(), Some(u64::MAX as u128));
+
+        // Orders that do not fit in a u128.
+        let mut trinomial = Gf2Poly::from_coefficients(&[1, 1]);
+        trinomial.toggle_coefficient(127);
+        assert_eq!(trinomial.order(), Some(u128::MAX >> 1));
+        let p = &trinomial * &Gf2Poly::from_coefficients(&[1, 1, 1]);
+        assert_eq!(p.order(), None);
+        let cube = &(&trinomial * &trinomial) * &trinomial;
+        assert_eq!(cube.order(), None);
+    }
+
+    #[test]
+    fn test_prime_factors() {
+        assert_eq!(
+            prime_factors(u64::MAX as u128),
+            vec![3, 5, 17, 257, 641, 65537, 6700417]
+        );
+        assert_eq!(prime_factors((1 << 61) - 1), vec![(1 << 61) - 1]);
+        assert_eq!(prime_factors((1 << 67) - 1), vec![193707721, 761838257287]);
+    }
+
+    #[test]
+    fn test_operators_and_display() {
+        let a = Gf2Poly::from_coefficients(&[1, 1, 0, 1]);
+        let b = Gf2Poly::from_coefficients(&[1, 1]);
+        assert_eq!(&a + &b, Gf2Poly::monomial(3));
+        assert_eq!(&(&a * &b) / &b, a);
+        assert_eq!(&a % &b, Gf2Poly::one());
+        assert_eq!(a.clone() + b.clone(), Gf2Poly::monomial(3));
+        assert_eq!(a.to_string(), "x^3 + x + 1");
+        assert_eq!(Gf2Poly::zero().to_string(), "0");
+        assert_eq!(Gf2Poly::from_words(vec![0b1011, 0, 0]), a);
+    }
+
+    #[test]
+    fn test_extended_gcd_and_inverse() {
+        let a = Gf2Poly::from_coefficients(&[1, 0, 1, 1, 0, 1, 1]);
+        let b = Gf2Poly::from_coefficients(&[0, 1, 1, 0, 1]);
+        let (g, s, t) = a.extended_gcd(&b);
+        assert_eq!(&s.mul(&a) + &t.mul(&b), g);
+        assert_eq!(g, a.gcd(&b));
+
+        let modulus = &Gf2Poly::monomial(7) + &Gf2Poly::one();
+        let x_plus_one = Gf2Poly::from_coefficients(&[1, 1]);
+        assert_eq!(x_plus_one.inverse_mod(&modulus), None);
+        let field_modulus = Gf2Poly::from_coefficients(&[1, 1, 0, 1]);
+        let c = Gf2Poly::from_coefficients(&[1, 0, 1]);
+        let inverse = c.inverse_mod(&field_modulus).unwrap();
+        assert_eq!(c.mul_mod(&inverse, &field_modulus), Gf2Poly::one());
+        assert_eq!(b.inverse_mod(&field_modulus), None);
+    }
+
+    #[test]
+    fn test_irreducibility() {
+        let irreducible_of_degree = |n: usize| {
+            (1u64 << n..1u64 << (n + 1))
+                .filter(|&w| Gf2Poly::from_words(vec![w]).is_irreducible())
+                .count()
+        };
+        // Number of irreducible polynomials of degree 1..=8 over GF(2).
+        let counts: Vec<usize> = (1..=8).map(irreducible_of_degree).collect();
+        assert_eq!(counts, vec![2, 1, 2, 3, 6, 9, 18, 30]);
+
+        // Irreducibility agrees with factorization.
+        for w in 2u64..512 {
+            let p = Gf2Poly::from_words(vec![w]);
+            let factors = p.factor();
+            let irreducible = factors.len() == 1 && factors[0].1 == 1;
+            assert_eq!(p.is_irreducible(), irreducible, "{}", p);
+        }
+
+        assert!(Gf2Poly::from_coefficients(&[1, 1, 0, 0, 1]).is_primitive());
+        assert!(!Gf2Poly::from_coefficients(&[1, 1, 1, 1, 1]).is_primitive());
+    }
+
+    #[test]
+    fn test_multiplication_matrix() {
+        let modulus = Gf2Poly::from_coefficients(&[1, 0, 1, 1, 0, 0, 0, 1]);
+        let a = Gf2Poly::from_coefficients(&[1, 1, 0, 0, 1, 0, 1]);
+        let m = a.multiplication_matrix::<u8>(&modulus);
+        for w in 0u8..128 {
+            let b = Gf2Poly::from_packed(w, 7);
+            assert_eq!(b.to_packed::<u8>(7), w);
+            assert_eq!(m.matrix_by_vector(&w), a.mul_mod(&b, &modulus).to_packed(7));
+        }
+        assert_eq!(Gf2Poly::from_multiplication_matrix(&m, &modulus), a);
+
+        // Circulant: every row is the previous one cyclically shifted right.
+        let a = Gf2Poly::from_coefficients(&[1, 1, 0, 1]);
+        let c = a.circulant_matrix::<u8>(5);
+        assert_eq!(
+            c,
+            PackedGF2Matrix::new(vec![0b11010, 0b01101, 0b10110, 0b01011, 0b10101], 5)
+        );
+        // Both circulant types expand the same polynomial to the same matrix.
+        let circulant = CirculantMatrix::new(a.clone(), 5);
+        assert_eq!(c, circulant.to_packed_matrix());
+        assert_eq!(
+            GF2Matrix::from(&c).elements,
+            circulant.to_gf2_matrix().elements
+        );
+        let cyclic = &Gf2Poly::monomial(5) + &Gf2Poly::one();
+        assert_eq!(c.transpose(), a.multiplication_matrix(&cyclic));
+    }
+}
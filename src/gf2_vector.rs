@@ -0,0 +1,530 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use core::ops::{BitAnd, BitAndAssign, BitXor, BitXorAssign, Range};
+
+use crate::matrix::{MatrixCommon, MatrixTrait, Number};
+use crate::packed_gf2_matrix::PackedGF2Matrix;
+use crate::simd;
+use crate::GF2Matrix;
+
+const WORD_BITS: usize = 64;
+
+/// A vector over GF(2) of a given length, stored in bit-packed form.
+///
+/// Entry `i` is bit `i % 64` of word `i / 64`, as for the coefficients of
+/// [`Gf2Poly`](crate::gf2_poly::Gf2Poly). The bits of the last word beyond the
+/// length are kept at zero, so equality is a plain comparison of the words.
+///
+/// Unlike the `Vec<u8>` vectors of [`GF2Matrix`] and the single words of
+/// [`PackedGF2Matrix`], a `Gf2Vector` carries its length, and operations on
+/// vectors of different lengths panic instead of silently truncating.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::gf2_vector::Gf2Vector;
+/// let u = Gf2Vector::from_bits(&[1, 0, 1, 1]);
+/// let v = Gf2Vector::from_bits(&[0, 0, 1, 1]);
+///
+/// assert_eq!(&u ^ &v, Gf2Vector::from_bits(&[1, 0, 0, 0]));
+/// assert_eq!(u.dot(&v), 0);
+/// assert_eq!(u.support(), vec![0, 2, 3]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Gf2Vector {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Gf2Vector {
+    /// Returns the zero vector of length `len`.
+    pub fn zeros(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(WORD_BITS)],
+            len,
+        }
+    }
+
+    /// Returns the vector of length `len` whose only one is at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn unit(len: usize, index: usize) -> Self {
+        let mut v = Self::zeros(len);
+        v.set(index, 1);
+        v
+    }
+
+    /// Creates a vector from its entries, each equal to `0` or `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an entry is neither `0` nor `1`.
+    pub fn from_bits(bits: &[u8]) -> Self {
+        let mut v = Self::zeros(bits.len());
+        for (i, &bit) in bits.iter().enumerate() {
+            assert!(bit <= 1, "entries of a GF(2) vector must be 0 or 1");
+            v.words[i / WORD_BITS] |= u64::from(bit) << (i % WORD_BITS);
+        }
+        v
+    }
+
+    /// Creates a vector of length `len` from a packed word, entry 0 being bit
+    /// `len - 1` as in the rows of [`PackedGF2Matrix`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is larger than the width of `T`.
+    pub fn from_packed<T: Number>(value: T, len: usize) -> Self {
+        assert!(
+            len <= core::mem::size_of::<T>() * 8,
+            "{} entries do not fit in the packed type",
+            len
+        );
+        let mut v = Self::zeros(len);
+        for i in 0..len {
+            if (value >> (len - 1 - i)) & T::one() != T::zero() {
+                v.words[i / WORD_BITS] |= 1 << (i % WORD_BITS);
+            }
+        }
+        v
+    }
+
+    /// Returns the entries of the vector, each equal to `0` or `1`.
+    pub fn to_bits(&self) -> Vec<u8> {
+        self.iter().collect()
+    }
+
+    /// Packs the vector in a word, entry 0 being bit `len - 1` as in the rows
+    /// of [`PackedGF2Matrix`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector is longer than the width of `T`.
+    pub fn to_packed<T: Number>(&self) -> T {
+        assert!(
+            self.len <= core::mem::size_of::<T>() * 8,
+            "{} entries do not fit in the packed type",
+            self.len
+        );
+        self.ones()
+            .fold(T::zero(), |acc, i| acc ^ (T::one() << (self.len - 1 - i)))
+    }
+
+    /// Returns the length of the vector.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the vector has length zero.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if every entry is zero.
+    pub fn is_zero(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Returns the packed words, entry `i` being bit `i % 64` of word `i / 64`.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    fn check_index(&self, index: usize) {
+        assert!(
+            index < self.len,
+            "index out of bounds: index is {}, but vector has length {}",
+            index,
+            self.len
+        );
+    }
+
+    fn check_len(&self, other: &Gf2Vector) {
+        assert_eq!(self.len, other.len, "vectors must have the same length");
+    }
+
+    /// Returns entry `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> u8 {
+        self.check_index(index);
+        ((self.words[index / WORD_BITS] >> (index % WORD_BITS)) & 1) as u8
+    }
+
+    /// Sets entry `index` to `value`, which must be `0` or `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds or `value` is neither `0` nor `1`.
+    pub fn set(&mut self, index: usize, value: u8) {
+        self.check_index(index);
+        assert!(value <= 1, "entries of a GF(2) vector must be 0 or 1");
+        let word = &mut self.words[index / WORD_BITS];
+        *word = (*word & !(1 << (index % WORD_BITS))) | (u64::from(value) << (index % WORD_BITS));
+    }
+
+    /// Flips entry `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn flip(&mut self, index: usize) {
+        self.check_index(index);
+        self.words[index / WORD_BITS] ^= 1 << (index % WORD_BITS);
+    }
+
+    /// Returns the dot product of two vectors over GF(2).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vectors have different lengths.
+    pub fn dot(&self, other: &Gf2Vector) -> u8 {
+        self.check_len(other);
+        simd::dot_parity(&self.words, &other.words)
+    }
+
+    /// Returns the Hamming weight, the number of ones of the vector.
+    pub fn weight(&self) -> usize {
+        simd::popcount(&self.words) as usize
+    }
+
+    /// Returns the indices of the ones of the vector, in increasing order.
+    pub fn support(&self) -> Vec<usize> {
+        self.ones().collect()
+    }
+
+    /// Iterates over the indices of the ones of the vector, in increasing order.
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(k, &word)| {
+            let mut rest = word;
+            core::iter::from_fn(move || {
+                if rest == 0 {
+                    return None;
+                }
+                let bit = rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                Some(k * WORD_BITS + bit)
+            })
+        })
+    }
+
+    /// Iterates over the entries of the vector.
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..self.len).map(|i| ((self.words[i / WORD_BITS] >> (i % WORD_BITS)) & 1) as u8)
+    }
+
+    /// Returns the 64 entries starting at `start` as a word, padded with zeros
+    /// past the end of the vector.
+    fn word_at(&self, start: usize) -> u64 {
+        let (k, offset) = (start / WORD_BITS, start % WORD_BITS);
+        let low = self.words.get(k).map_or(0, |&w| w >> offset);
+        let high = match offset {
+            0 => 0,
+            _ => self
+                .words
+                .get(k + 1)
+                .map_or(0, |&w| w << (WORD_BITS - offset)),
+        };
+        low | high
+    }
+
+    /// Clears the bits of the last word beyond the length.
+    fn clear_padding(&mut self) {
+        if !self.len.is_multiple_of(WORD_BITS) {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << (self.len % WORD_BITS)) - 1;
+            }
+        }
+    }
+
+    /// Returns the vector made of the entries in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds or decreasing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_vector::Gf2Vector;
+    /// let v = Gf2Vector::from_bits(&[1, 0, 1, 1, 0]);
+    /// assert_eq!(v.slice(1..4), Gf2Vector::from_bits(&[0, 1, 1]));
+    /// ```
+    pub fn slice(&self, range: Range<usize>) -> Gf2Vector {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "range {}..{} out of bounds for a vector of length {}",
+            range.start,
+            range.end,
+            self.len
+        );
+        let len = range.end - range.start;
+        let mut result = Gf2Vector {
+            words: (0..len.div_ceil(WORD_BITS))
+                .map(|k| self.word_at(range.start + k * WORD_BITS))
+                .collect(),
+            len,
+        };
+        result.clear_padding();
+        result
+    }
+
+    /// Returns the concatenation of this vector and `other`, `[self | other]`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_vector::Gf2Vector;
+    /// let u = Gf2Vector::from_bits(&[1, 0]);
+    /// let v = Gf2Vector::from_bits(&[1, 1, 0]);
+    /// assert_eq!(u.concat(&v), Gf2Vector::from_bits(&[1, 0, 1, 1, 0]));
+    /// ```
+    pub fn concat(&self, other: &Gf2Vector) -> Gf2Vector {
+        let len = self.len + other.len;
+        let mut words = self.words.clone();
+        words.resize(len.div_ceil(WORD_BITS), 0);
+        let (k, offset) = (self.len / WORD_BITS, self.len % WORD_BITS);
+        for (j, &word) in other.words.iter().enumerate() {
+            words[k + j] |= word << offset;
+            if offset != 0 && k + j + 1 < words.len() {
+                words[k + j + 1] |= word >> (WORD_BITS - offset);
+            }
+        }
+        Gf2Vector { words, len }
+    }
+}
+
+impl fmt::Display for Gf2Vector {
+    /// Formats the entries as a string of `0` and `1`, entry 0 first.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for bit in self.iter() {
+            write!(f, "{}", bit)?;
+        }
+        Ok(())
+    }
+}
+
+impl BitXorAssign<&Gf2Vector> for Gf2Vector {
+    fn bitxor_assign(&mut self, rhs: &Gf2Vector) {
+        self.check_len(rhs);
+        simd::xor_into(&mut self.words, &rhs.words);
+    }
+}
+
+impl BitXor<&Gf2Vector> for &Gf2Vector {
+    type Output = Gf2Vector;
+
+    fn bitxor(self, rhs: &Gf2Vector) -> Gf2Vector {
+        let mut result = self.clone();
+        result ^= rhs;
+        result
+    }
+}
+
+impl BitXor for Gf2Vector {
+    type Output = Gf2Vector;
+
+    fn bitxor(mut self, rhs: Gf2Vector) -> Gf2Vector {
+        self ^= &rhs;
+        self
+    }
+}
+
+impl BitAndAssign<&Gf2Vector> for Gf2Vector {
+    fn bitand_assign(&mut self, rhs: &Gf2Vector) {
+        self.check_len(rhs);
+        for (x, y) in self.words.iter_mut().zip(&rhs.words) {
+            *x &= y;
+        }
+    }
+}
+
+impl BitAnd<&Gf2Vector> for &Gf2Vector {
+    type Output = Gf2Vector;
+
+    fn bitand(self, rhs: &Gf2Vector) -> Gf2Vector {
+        let mut result = self.clone();
+        result &= rhs;
+        result
+    }
+}
+
+impl BitAnd for Gf2Vector {
+    type Output = Gf2Vector;
+
+    fn bitand(mut self, rhs: Gf2Vector) -> Gf2Vector {
+        self &= &rhs;
+        self
+    }
+}
+
+impl GF2Matrix {
+    /// Returns the product `self * v`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v.len() != self.ncols()`.
+    pub fn mul_vector(&self, v: &Gf2Vector) -> Gf2Vector {
+        assert_eq!(
+            v.len(),
+            self.ncols(),
+            "vector length must equal the number of columns"
+        );
+        let mut result = Gf2Vector::zeros(self.nrows());
+        for (i, row) in self.elements.iter().enumerate() {
+            let dot = v.ones().fold(0, |acc, j| acc ^ row[j]);
+            result.set(i, dot);
+        }
+        result
+    }
+
+    /// Solves `self * x = b` like [`GF2Matrix::solve`], with vectors that carry
+    /// their length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b.len() != self.nrows()` or the matrix does not have full
+    /// column rank.
+    pub fn solve_vector(&self, b: &Gf2Vector) -> Gf2Vector {
+        assert_eq!(
+            b.len(),
+            self.nrows(),
+            "right-hand side length must equal the number of rows"
+        );
+        Gf2Vector::from_bits(&self.solve(&b.to_bits()))
+    }
+
+    /// Returns a basis of the kernel, as [`MatrixTrait::kernel`].
+    pub fn kernel_vectors(&self) -> Vec<Gf2Vector> {
+        self.kernel()
+            .iter()
+            .map(|k| Gf2Vector::from_bits(k))
+            .collect()
+    }
+
+    /// Returns a basis of the image (the row space), as [`MatrixTrait::image`].
+    pub fn image_vectors(&self) -> Vec<Gf2Vector> {
+        self.image()
+            .iter()
+            .map(|r| Gf2Vector::from_bits(r))
+            .collect()
+    }
+}
+
+impl<T: Number> PackedGF2Matrix<T> {
+    /// Returns the product `self * v`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v.len() != self.ncols()`.
+    pub fn mul_vector(&self, v: &Gf2Vector) -> Gf2Vector {
+        assert_eq!(
+            v.len(),
+            self.ncols(),
+            "vector length must equal the number of columns"
+        );
+        let packed: T = v.to_packed();
+        let mut result = Gf2Vector::zeros(self.nrows());
+        for i in 0..self.nrows() {
+            result.set(i, ((self.row(i) & packed).count_ones() % 2) as u8);
+        }
+        result
+    }
+
+    /// Solves `self * x = b` like [`PackedGF2Matrix::solve`], with vectors that
+    /// carry their length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b.len() != self.nrows()`, or in the cases of
+    /// [`PackedGF2Matrix::solve`].
+    pub fn solve_vector(&self, b: &Gf2Vector) -> Gf2Vector {
+        assert_eq!(
+            b.len(),
+            self.nrows(),
+            "right-hand side length must equal the number of rows"
+        );
+        Gf2Vector::from_packed(self.solve(b.to_packed()), self.ncols())
+    }
+
+    /// Returns a basis of the kernel, as [`PackedGF2Matrix::kernel`].
+    pub fn kernel_vectors(&self) -> Vec<Gf2Vector> {
+        self.kernel()
+            .into_iter()
+            .map(|k| Gf2Vector::from_packed(k, self.ncols()))
+            .collect()
+    }
+
+    /// Returns a basis of the image (the row space), as
+    /// [`PackedGF2Matrix::image`].
+    pub fn image_vectors(&self) -> Vec<Gf2Vector> {
+        self.image()
+            .into_iter()
+            .map(|r| Gf2Vector::from_packed(r, self.ncols()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slice_and_concat_across_words() {
+        let bits: Vec<u8> = (0..150).map(|i| ((i * 7 + i / 5) % 3 == 0) as u8).collect();
+        let v = Gf2Vector::from_bits(&bits);
+        assert_eq!(v.to_bits(), bits);
+        assert_eq!(v.weight(), bits.iter().filter(|&&b| b == 1).count());
+        assert_eq!(
+            v.support(),
+            (0..150).filter(|&i| bits[i] == 1).collect::<Vec<_>>()
+        );
+
+        for (start, end) in [(0, 150), (3, 70), (64, 128), (10, 10), (61, 150)] {
+            let s = v.slice(start..end);
+            assert_eq!(s.to_bits(), bits[start..end]);
+            assert_eq!(v.slice(0..start).concat(&s).concat(&v.slice(end..150)), v);
+        }
+
+        // `u` differs from `v` in its last entry only.
+        let tail = &v.slice(75..150) ^ &Gf2Vector::unit(75, 74);
+        let u = Gf2Vector::from_bits(&bits[..75]).concat(&tail);
+        assert_eq!(u.len(), 150);
+        assert_eq!((&u ^ &v).support(), vec![149]);
+        let common = v.weight() - bits[149] as usize;
+        assert_eq!((&u & &v).weight(), common);
+        assert_eq!(u.dot(&v), (common % 2) as u8);
+    }
+
+    #[test]
+    fn test_matrix_vector_apis_agree() {
+        let dense = GF2Matrix::new(vec![vec![1, 0, 0], vec![0, 1, 1], vec![1, 0, 1]]);
+        let packed = PackedGF2Matrix::<u8>::from(&dense);
+        let b = Gf2Vector::from_bits(&[0, 0, 1]);
+
+        let x = dense.solve_vector(&b);
+        assert_eq!(x, Gf2Vector::from_bits(&[0, 1, 1]));
+        assert_eq!(packed.solve_vector(&b), x);
+        assert_eq!(dense.mul_vector(&x), b);
+        assert_eq!(packed.mul_vector(&x), b);
+
+        let singular = GF2Matrix::new(vec![vec![1, 0, 0, 0], vec![0, 1, 0, 1]]);
+        let packed = PackedGF2Matrix::<u16>::from(&singular);
+        assert_eq!(packed.kernel_vectors(), singular.kernel_vectors());
+        assert_eq!(packed.image_vectors(), singular.image_vectors());
+        for k in singular.kernel_vectors() {
+            assert!(singular.mul_vector(&k).is_zero());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "right-hand side length must equal the number of rows")]
+    fn test_solve_vector_rejects_wrong_length() {
+        let m = PackedGF2Matrix::new(vec![0b10u8, 0b01], 2);
+        m.solve_vector(&Gf2Vector::from_bits(&[1, 0, 1]));
+    }
+}
@@ -0,0 +1,405 @@
+use alloc::collections::BTreeSet;
+use alloc::{vec, vec::Vec};
+
+use crate::boolean_poly::{BooleanPolynomial, Monomial, MonomialOrder};
+use crate::xl::echelonise;
+
+/// A critical pair of the F4 algorithm.
+#[derive(Clone, Copy, Debug)]
+enum Pair {
+    /// The S-polynomial of two basis elements.
+    Critical(usize, usize),
+    /// The S-polynomial of a basis element and the field equation
+    /// `x_i^2 + x_i` of a variable of its leading monomial.
+    Field(usize, usize),
+}
+
+/// Computes the reduced Gröbner basis of an ideal of the boolean polynomial
+/// ring with the F4 algorithm.
+///
+/// The field equations `x_i^2 = x_i` are part of the ideal: the result is the
+/// boolean part of a Gröbner basis of `polys` together with the field
+/// equations. Critical pairs of least degree are processed together: the
+/// products are collected by symbolic preprocessing into one Macaulay-style
+/// matrix, which is reduced in a single echelon form on packed rows
+/// ([`crate::packed_gf2_matrix::PackedGF2Matrix::echelon_form_in_place_with_ops`]
+/// when the monomials fit in a `u64` or `u128`, rows of several words added
+/// with [`crate::simd::xor_into`] beyond).
+///
+/// # Arguments
+///
+/// * `polys` - generators of the ideal.
+/// * `order` - the monomial order of the basis.
+///
+/// # Returns
+///
+/// The reduced Gröbner basis, sorted by decreasing leading monomial. It is
+/// `[1]` if the system has no solution and empty if all generators are zero.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::boolean_poly::{BooleanPolynomial, MonomialOrder};
+/// # use lin_algebra::groebner::groebner_basis;
+/// let x = BooleanPolynomial::variable;
+/// // x0*x1 + x2, x0 + x1 + 1
+/// let polys = [&(&x(0) * &x(1)) + &x(2), &(&x(0) + &x(1)) + &BooleanPolynomial::one()];
+///
+/// let basis = groebner_basis(&polys, MonomialOrder::Lex);
+/// // x0 = x1 + 1 gives x0*x1 = x1 + x1 = 0, hence x2 = 0.
+/// let strings: Vec<String> = basis.iter().map(|p| p.to_string()).collect();
+/// assert_eq!(strings, vec!["x0 + x1 + 1", "x2"]);
+/// ```
+pub fn groebner_basis(polys: &[BooleanPolynomial], order: MonomialOrder) -> Vec<BooleanPolynomial> {
+    let mut basis: Vec<BooleanPolynomial> = Vec::new();
+    let mut leads: Vec<Monomial> = Vec::new();
+    let mut pairs: Vec<Pair> = Vec::new();
+
+    let mut new_elements = echelonise(polys, order);
+    loop {
+        for h in new_elements {
+            let lead = h.leading_monomial(order).unwrap();
+            if lead == Monomial::one() {
+                return vec![BooleanPolynomial::one()];
+            }
+            let k = basis.len();
+            for (j, &other) in leads.iter().enumerate() {
+                // Buchberger's product criterion: coprime leading monomials
+                // give S-polynomials reducing to zero.
+                if lead.bits() & other.bits() != 0 {
+                    pairs.push(Pair::Critical(j, k));
+                }
+            }
+            for i in lead.variables() {
+                pairs.push(Pair::Field(k, i));
+            }
+            basis.push(h);
+            leads.push(lead);
+        }
+
+        let Some(degree) = pairs.iter().map(|&p| pair_degree(p, &leads)).min() else {
+            break;
+        };
+        let (selected, rest): (Vec<Pair>, Vec<Pair>) = pairs
+            .into_iter()
+            .partition(|&p| pair_degree(p, &leads) == degree);
+        pairs = rest;
+
+        // Left and right halves of the selected S-polynomials.
+        let mut rows = Vec::new();
+        for pair in selected {
+            match pair {
+                Pair::Critical(j, k) => {
+                    let lcm = leads[j].lcm(&leads[k]);
+                    for i in [j, k] {
+                        let multiplier = leads[i].divide(&lcm).unwrap();
+                        rows.push(basis[i].mul_monomial(multiplier));
+                    }
+                }
+                Pair::Field(k, i) => {
+                    rows.push(basis[k].mul_monomial(Monomial::variable(i)));
+                    rows.push(basis[k].clone());
+                }
+            }
+        }
+
+        symbolic_preprocessing(&mut rows, &basis, &leads);
+
+        new_elements = echelonise(&rows, order)
+            .into_iter()
+            .filter(|row| {
+                let lead = row.leading_monomial(order).unwrap();
+                !leads.iter().any(|l| l.divides(&lead))
+            })
+            .collect();
+    }
+
+    reduce_basis(basis, order)
+}
+
+/// Returns `true` if `basis` is a Gröbner basis of the ideal it generates
+/// together with the field equations, i.e. if every S-polynomial reduces to
+/// zero.
+pub fn is_groebner_basis(basis: &[BooleanPolynomial], order: MonomialOrder) -> bool {
+    let leads: Vec<Monomial> = basis
+        .iter()
+        .filter_map(|g| g.leading_monomial(order))
+        .collect();
+    if leads.len() != basis.len() {
+        return false;
+    }
+
+    for (k, g) in basis.iter().enumerate() {
+        for i in leads[k].variables() {
+            let s = &g.mul_monomial(Monomial::variable(i)) + g;
+            if !normal_form(&s, basis, order).is_zero() {
+                return false;
+            }
+        }
+        for j in 0..k {
+            let lcm = leads[j].lcm(&leads[k]);
+            let s = &basis[j].mul_monomial(leads[j].divide(&lcm).unwrap())
+                + &g.mul_monomial(leads[k].divide(&lcm).unwrap());
+            if !normal_form(&s, basis, order).is_zero() {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Computes the normal form of `p` with respect to `basis`: the remainder of the
+/// full multivariate division of `p` by the elements of `basis`.
+///
+/// If `basis` is a Gröbner basis, the normal form is zero exactly when `p` is in
+/// the ideal.
+pub fn normal_form(
+    p: &BooleanPolynomial,
+    basis: &[BooleanPolynomial],
+    order: MonomialOrder,
+) -> BooleanPolynomial {
+    let reducers: Vec<(Monomial, &BooleanPolynomial)> = basis
+        .iter()
+        .filter_map(|g| g.leading_monomial(order).map(|l| (l, g)))
+        .collect();
+
+    let mut p = p.clone();
+    // Reduce the largest reducible monomial first: reductions only introduce
+    // smaller monomials, so this terminates.
+    while let Some((m, (lead, g))) = p.sorted_monomials(order).into_iter().find_map(|m| {
+        reducers
+            .iter()
+            .find(|(l, _)| l.divides(&m))
+            .map(|&r| (m, r))
+    }) {
+        p += &g.mul_monomial(lead.divide(&m).unwrap());
+    }
+    p
+}
+
+/// Enumerates the common zeros in GF(2)^nvars of a set of boolean polynomials.
+///
+/// The variables are assigned from `x_{nvars-1}` down to `x_0`, and every
+/// polynomial is checked as soon as all its variables are assigned. For a
+/// lexicographic Gröbner basis this search never backtracks, since the basis
+/// contains a basis of every elimination ideal.
+///
+/// # Returns
+///
+/// The solutions, as vectors of `nvars` bits in lexicographic order.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::boolean_poly::{BooleanPolynomial, MonomialOrder};
+/// # use lin_algebra::groebner::{groebner_basis, variety};
+/// let x = BooleanPolynomial::variable;
+/// let polys = [&(&x(0) * &x(1)) + &x(2), &(&x(0) + &x(1)) + &BooleanPolynomial::one()];
+///
+/// let basis = groebner_basis(&polys, MonomialOrder::Lex);
+/// assert_eq!(variety(&basis, 3), vec![vec![0, 1, 0], vec![1, 0, 0]]);
+/// ```
+pub fn variety(polys: &[BooleanPolynomial], nvars: usize) -> Vec<Vec<u8>> {
+    assert!(
+        nvars <= 64,
+        "boolean polynomials support at most 64 variables"
+    );
+    for p in polys {
+        assert!(
+            p.nvars() <= nvars,
+            "polynomial uses more than nvars variables"
+        );
+    }
+
+    // checks[k] holds the polynomials whose smallest variable is x_k; constant
+    // polynomials are checked before any assignment.
+    let mut checks: Vec<Vec<&BooleanPolynomial>> = vec![Vec::new(); nvars];
+    for p in polys {
+        let all = p.monomials().fold(0u64, |acc, m| acc | m.bits());
+        if all == 0 {
+            if !p.is_zero() {
+                return Vec::new();
+            }
+        } else {
+            checks[all.trailing_zeros() as usize].push(p);
+        }
+    }
+
+    let mut solutions = Vec::new();
+    let mut stack = vec![(nvars, 0u64)];
+    while let Some((assigned, point)) = stack.pop() {
+        if assigned == 0 {
+            solutions.push(point);
+            continue;
+        }
+        let k = assigned - 1;
+        for value in [1u64, 0] {
+            let point = point | (value << k);
+            if checks[k].iter().all(|p| p.evaluate_bits(point) == 0) {
+                stack.push((k, point));
+            }
+        }
+    }
+
+    solutions.sort_unstable_by_key(|&point| point.reverse_bits());
+    solutions
+        .into_iter()
+        .map(|point| (0..nvars).map(|v| ((point >> v) & 1) as u8).collect())
+        .collect()
+}
+
+/// Returns the degree of the least common multiple of a pair in the polynomial
+/// ring, used to select the pairs of a step.
+fn pair_degree(pair: Pair, leads: &[Monomial]) -> usize {
+    match pair {
+        Pair::Critical(j, k) => leads[j].lcm(&leads[k]).degree(),
+        Pair::Field(k, _) => leads[k].degree() + 1,
+    }
+}
+
+/// Adds to `rows` a reducer `(m / LM(g)) * g` for every monomial `m` of the rows
+/// that is divisible by a leading monomial of the basis.
+fn symbolic_preprocessing(
+    rows: &mut Vec<BooleanPolynomial>,
+    basis: &[BooleanPolynomial],
+    leads: &[Monomial],
+) {
+    let mut seen: BTreeSet<Monomial> = BTreeSet::new();
+    let mut queue: Vec<Monomial> = Vec::new();
+    for row in rows.iter() {
+        for m in row.monomials() {
+            if seen.insert(m) {
+                queue.push(m);
+            }
+        }
+    }
+
+    while let Some(m) = queue.pop() {
+        let Some(i) = leads.iter().position(|l| l.divides(&m)) else {
+            continue;
+        };
+        let reducer = basis[i].mul_monomial(leads[i].divide(&m).unwrap());
+        for t in reducer.monomials() {
+            if seen.insert(t) {
+                queue.push(t);
+            }
+        }
+        rows.push(reducer);
+    }
+}
+
+/// Turns a Gröbner basis into the reduced Gröbner basis: drops the elements
+/// whose leading monomial is divisible by another one and reduces the tails.
+fn reduce_basis(basis: Vec<BooleanPolynomial>, order: MonomialOrder) -> Vec<BooleanPolynomial> {
+    let mut sorted: Vec<(Monomial, BooleanPolynomial)> = basis
+        .into_iter()
+        .map(|g| (g.leading_monomial(order).unwrap(), g))
+        .collect();
+    sorted.sort_by(|a, b| order.compare(a.0, b.0));
+
+    let mut minimal: Vec<(Monomial, BooleanPolynomial)> = Vec::new();
+    for (lead, g) in sorted {
+        if !minimal.iter().any(|(l, _)| l.divides(&lead)) {
+            minimal.push((lead, g));
+        }
+    }
+
+    let mut reduced: Vec<BooleanPolynomial> = (0..minimal.len())
+        .map(|i| {
+            let others: Vec<BooleanPolynomial> = minimal
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, (_, g))| g.clone())
+                .collect();
+            let (lead, g) = &minimal[i];
+            let mut tail = g.clone();
+            tail.toggle(*lead);
+            let mut h = normal_form(&tail, &others, order);
+            h.toggle(*lead);
+            h
+        })
+        .collect();
+    reduced.reverse();
+    reduced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_rng::xorshift64;
+
+    fn random_system(nvars: usize, count: usize, seed: u64) -> Vec<BooleanPolynomial> {
+        let mut state = seed;
+        (0..count)
+            .map(|_| {
+                let monomials: Vec<Monomial> = (0..1u64 << nvars)
+                    .filter(|m| m.count_ones() <= 2)
+                    .filter(|_| xorshift64(&mut state).is_multiple_of(3))
+                    .map(Monomial::from_bits)
+                    .collect();
+                BooleanPolynomial::from_monomials(&monomials)
+            })
+            .collect()
+    }
+
+    fn brute_force(polys: &[BooleanPolynomial], nvars: usize) -> Vec<Vec<u8>> {
+        let mut solutions: Vec<Vec<u8>> = (0..1u64 << nvars)
+            .filter(|&point| polys.iter().all(|p| p.evaluate_bits(point) == 0))
+            .map(|point| (0..nvars).map(|v| ((point >> v) & 1) as u8).collect())
+            .collect();
+        solutions.sort();
+        solutions
+    }
+
+    #[test]
+    fn test_groebner_basis_is_reduced_and_generates_the_ideal() {
+        for order in [
+            MonomialOrder::Lex,
+            MonomialOrder::DegLex,
+            MonomialOrder::DegRevLex,
+        ] {
+            for seed in 1..8 {
+                let polys = random_system(5, 4, seed);
+                let basis = groebner_basis(&polys, order);
+
+                assert!(is_groebner_basis(&basis, order));
+                for p in &polys {
+                    assert!(normal_form(p, &basis, order).is_zero());
+                }
+                // Reduced: no monomial of an element is divisible by the leading
+                // monomial of another element.
+                for (i, g) in basis.iter().enumerate() {
+                    for (j, h) in basis.iter().enumerate() {
+                        let lead = h.leading_monomial(order).unwrap();
+                        if i != j {
+                            assert!(g.monomials().all(|m| !lead.divides(&m)));
+                        }
+                    }
+                }
+                assert_eq!(variety(&basis, 5), brute_force(&polys, 5));
+            }
+        }
+    }
+
+    #[test]
+    fn test_groebner_basis_of_a_point_and_of_an_empty_variety() {
+        let x = BooleanPolynomial::variable;
+        let one = BooleanPolynomial::one();
+
+        // The ideal of the single point (1, 0, 1) is generated by x0 + 1, x1, x2 + 1.
+        let polys = [
+            &(&x(0) * &x(1)) + &x(1),
+            &(&x(0) + &x(2)) + &(&x(1) * &x(2)),
+            &(&x(0) * &x(2)) + &one,
+        ];
+        let basis = groebner_basis(&polys, MonomialOrder::DegRevLex);
+        let strings: Vec<String> = basis.iter().map(|p| p.to_string()).collect();
+        assert_eq!(strings, vec!["x0 + 1", "x1", "x2 + 1"]);
+
+        // x0*x1 = 1 forces x0 = x1 = 1, contradicting x0 + x1 = 1.
+        let polys = [&(&x(0) * &x(1)) + &one, &(&x(0) + &x(1)) + &one];
+        assert_eq!(groebner_basis(&polys, MonomialOrder::Lex), vec![one]);
+        assert!(groebner_basis(&[], MonomialOrder::Lex).is_empty());
+    }
+}
@@ -0,0 +1,643 @@
+use crate::simd::xor_into;
+use crate::sparse_gf2_matrix::SparseGF2Matrix;
+use alloc::{vec, vec::Vec};
+
+/// Block size of the iterative solvers: block vectors hold 64 vectors of
+/// GF(2)^n, one per bit of a `u64`.
+const BLOCK: usize = 64;
+
+/// A matrix over GF(2) accessed only through products with block vectors.
+///
+/// A block vector of length `n` is a `&[u64]` with `n` entries; bit `k` of entry
+/// `i` is coordinate `i` of the `k`-th vector of the block. Implementations only
+/// need to multiply by such blocks, so the matrix may be stored in any form (or
+/// not stored at all).
+pub trait GF2BlackBox {
+    /// Returns the number of rows of the matrix.
+    fn nrows(&self) -> usize;
+
+    /// Returns the number of columns of the matrix.
+    fn ncols(&self) -> usize;
+
+    /// Computes `A x` for a block vector `x` of length `ncols`.
+    fn apply(&self, x: &[u64]) -> Vec<u64>;
+
+    /// Computes `A^T x` for a block vector `x` of length `nrows`.
+    fn apply_transpose(&self, x: &[u64]) -> Vec<u64>;
+}
+
+impl GF2BlackBox for SparseGF2Matrix {
+    fn nrows(&self) -> usize {
+        SparseGF2Matrix::nrows(self)
+    }
+
+    fn ncols(&self) -> usize {
+        SparseGF2Matrix::ncols(self)
+    }
+
+    fn apply(&self, x: &[u64]) -> Vec<u64> {
+        assert_eq!(
+            x.len(),
+            self.ncols(),
+            "block length must match the number of columns"
+        );
+        (0..self.nrows())
+            .map(|i| self.row(i).iter().fold(0, |acc, &j| acc ^ x[j]))
+            .collect()
+    }
+
+    fn apply_transpose(&self, x: &[u64]) -> Vec<u64> {
+        assert_eq!(
+            x.len(),
+            self.nrows(),
+            "block length must match the number of rows"
+        );
+        let mut y = vec![0u64; self.ncols()];
+        for (i, &xi) in x.iter().enumerate() {
+            for &j in self.row(i) {
+                y[j] ^= xi;
+            }
+        }
+        y
+    }
+}
+
+/// Computes kernel vectors of a large sparse matrix with Montgomery's block
+/// Lanczos algorithm.
+///
+/// The iteration runs on the symmetric matrix `A^T A` with blocks of 64 vectors
+/// and needs about `ncols / 63` iterations, each costing two black-box products.
+/// It is the scalable counterpart of the dense `kernel` methods for systems with
+/// millions of unknowns, but only finds *some* kernel vectors (typically up to
+/// a few dozen), not a full basis.
+///
+/// # Arguments
+///
+/// * `matrix` - the matrix, accessed through [`GF2BlackBox`].
+/// * `seed` - seed of the random starting block.
+///
+/// # Returns
+///
+/// Linearly independent nonzero vectors `x` with `A x = 0`, each checked by a
+/// multiplication with `matrix`, or `None` if the iteration broke down; a
+/// different seed usually succeeds.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::iterative_solvers::block_lanczos;
+/// # use lin_algebra::sparse_gf2_matrix::SparseGF2Matrix;
+/// let rows = (0..300).map(|i| vec![i, (7 * i + 1) % 400, (13 * i + 5) % 400]).collect();
+/// let m = SparseGF2Matrix::from_rows(400, rows);
+///
+/// let kernel = block_lanczos(&m, 1).unwrap();
+/// assert!(!kernel.is_empty());
+/// for v in &kernel {
+///     assert!(m.mul_vector(v).iter().all(|&b| b == 0));
+/// }
+/// ```
+pub fn block_lanczos<B: GF2BlackBox + ?Sized>(matrix: &B, seed: u64) -> Option<Vec<Vec<u8>>> {
+    let n = matrix.ncols();
+    let symmetric = |v: &[u64]| matrix.apply_transpose(&matrix.apply(v));
+    let mut state = seed;
+
+    // Solve A^T A x = A^T A y for a random y, so that x - y is in the kernel.
+    let y = random_block(n, &mut state);
+    let v0 = symmetric(&y);
+    let mut x = vec![0u64; n];
+
+    // v[0] = V_i, v[1] = V_{i-1}, v[2] = V_{i-2}.
+    let mut v = [v0.clone(), vec![0u64; n], vec![0u64; n]];
+    let mut winv1 = [0u64; BLOCK];
+    let mut winv2 = [0u64; BLOCK];
+    let mut vt_a_v1 = [0u64; BLOCK];
+    let mut vt_a2_v1 = [0u64; BLOCK];
+    let mut last_selected: Vec<usize> = (0..BLOCK).collect();
+
+    let max_iterations = n / (BLOCK - 4) + 20;
+    for iteration in 0.. {
+        if iteration > max_iterations {
+            return None;
+        }
+        if v[0].iter().all(|&w| w == 0) {
+            break;
+        }
+
+        let av = symmetric(&v[0]);
+        let vt_a_v = inner_product(&v[0], &av);
+        let vt_a2_v = inner_product(&av, &av);
+        if vt_a_v.iter().all(|&w| w == 0) {
+            break;
+        }
+
+        let (winv0, selected) = find_nonsingular_subset(&vt_a_v, &last_selected)?;
+        let mask0 = selected.iter().fold(0u64, |acc, &c| acc | (1 << c));
+        let mask1 = last_selected.iter().fold(0u64, |acc, &c| acc | (1 << c));
+
+        // X += V_i W_i^inv V_i^T V_0
+        let coefficients = mul_small(&winv0, &inner_product(&v[0], &v0));
+        xor_into(&mut x, &mul_block_small(&v[0], &coefficients));
+
+        // D = I - W_i^inv (V_i^T A^2 V_i S_i S_i^T + V_i^T A V_i)
+        let mut d = [0u64; BLOCK];
+        for i in 0..BLOCK {
+            d[i] = (vt_a2_v[i] & mask0) ^ vt_a_v[i];
+        }
+        let mut d = mul_small(&winv0, &d);
+        for (i, row) in d.iter_mut().enumerate() {
+            *row ^= 1 << i;
+        }
+
+        // E = -W_{i-1}^inv V_i^T A V_i S_i S_i^T
+        let mut e = mul_small(&winv1, &vt_a_v);
+        for row in e.iter_mut() {
+            *row &= mask0;
+        }
+
+        // F = -W_{i-2}^inv (I - V_{i-1}^T A V_{i-1} W_{i-1}^inv)
+        //     (V_{i-1}^T A^2 V_{i-1} S_{i-1} S_{i-1}^T + V_{i-1}^T A V_{i-1}) S_i S_i^T
+        let mut f = mul_small(&vt_a_v1, &winv1);
+        for (i, row) in f.iter_mut().enumerate() {
+            *row ^= 1 << i;
+        }
+        let f = mul_small(&winv2, &f);
+        let mut g = [0u64; BLOCK];
+        for i in 0..BLOCK {
+            g[i] = (vt_a2_v1[i] & mask1) ^ vt_a_v1[i];
+        }
+        let mut f = mul_small(&f, &g);
+        for row in f.iter_mut() {
+            *row &= mask0;
+        }
+
+        let mut next: Vec<u64> = av.iter().map(|&w| w & mask0).collect();
+        xor_into(&mut next, &mul_block_small(&v[0], &d));
+        xor_into(&mut next, &mul_block_small(&v[1], &e));
+        xor_into(&mut next, &mul_block_small(&v[2], &f));
+
+        v.rotate_right(1);
+        v[0] = next;
+        winv2 = winv1;
+        winv1 = winv0;
+        vt_a_v1 = vt_a_v;
+        vt_a2_v1 = vt_a2_v;
+        last_selected = selected;
+    }
+
+    xor_into(&mut x, &y);
+    Some(combine_kernel_vectors(matrix, &x, &v[0]))
+}
+
+/// Computes kernel vectors of a large sparse matrix with Coppersmith's block
+/// Wiedemann algorithm.
+///
+/// The algorithm computes the sequence `X^T A^i Y` of 64 x 64 matrices for
+/// random blocks `X` and `Y`, finds a matrix generator of the sequence with an
+/// approximant basis (matrix Berlekamp–Massey), and evaluates the generator at
+/// the matrix to produce kernel vectors. Non-square matrices are made square by
+/// padding with zero rows when they are wide and by working with `A^T A` when
+/// they are tall.
+///
+/// The sequence costs about `ncols / 32` black-box products; the generator
+/// computation is quadratic in `ncols / 64`, so [`block_lanczos`] is preferable
+/// for the very largest systems.
+///
+/// # Arguments
+///
+/// * `matrix` - the matrix, accessed through [`GF2BlackBox`].
+/// * `seed` - seed of the random blocks.
+///
+/// # Returns
+///
+/// Linearly independent nonzero vectors `x` with `A x = 0`, each checked by a
+/// multiplication with `matrix`, or `None` if no generator was found.
+pub fn block_wiedemann<B: GF2BlackBox + ?Sized>(matrix: &B, seed: u64) -> Option<Vec<Vec<u8>>> {
+    let n = matrix.ncols();
+    let square = |v: &[u64]| {
+        if matrix.nrows() > n {
+            matrix.apply_transpose(&matrix.apply(v))
+        } else {
+            let mut w = matrix.apply(v);
+            w.resize(n, 0);
+            w
+        }
+    };
+    let mut state = seed;
+
+    let x = random_block(n, &mut state);
+    let z = random_block(n, &mut state);
+    let length = 2 * n.div_ceil(BLOCK) + 10;
+    let mut sequence = Vec::with_capacity(length);
+    let mut v = square(&z);
+    for _ in 0..length {
+        sequence.push(inner_product(&x, &v));
+        v = square(&v);
+    }
+
+    let generators = matrix_generator(&sequence);
+    if generators.is_empty() {
+        return None;
+    }
+
+    // Evaluate every generator F(λ) = F_0 + ... + F_d λ^d at once, by Horner's
+    // rule, as u = A^d Z F_0 + ... + Z F_d. Shorter generators are aligned on
+    // the highest degree so that they all end at the same step.
+    let degree = generators.iter().map(|f| f.len() - 1).max().unwrap();
+    let mut u = vec![0u64; n];
+    for j in 0..=degree {
+        u = square(&u);
+        let mut coefficients = [0u64; BLOCK];
+        for (column, generator) in generators.iter().enumerate() {
+            let shift = degree + 1 - generator.len();
+            if j >= shift {
+                let mut bits = generator[j - shift];
+                while bits != 0 {
+                    let k = bits.trailing_zeros() as usize;
+                    coefficients[k] |= 1 << column;
+                    bits &= bits - 1;
+                }
+            }
+        }
+        xor_into(&mut u, &mul_block_small(&z, &coefficients));
+    }
+
+    let au = square(&u);
+    Some(combine_kernel_vectors(matrix, &u, &au))
+}
+
+/// Computes matrix generators of the sequence `a_i` of 64 x 64 matrices.
+///
+/// An order-`L` approximant basis of `[A(λ) | I]`, with `A(λ) = Σ a_i λ^i`, is
+/// built one order at a time. Its columns `(F, G)` satisfy
+/// `A(λ) F(λ) + G(λ) ≡ 0 mod λ^L`; those with `deg G < deg F = d` give
+/// relations `Σ_k a_{i+k} F_{d-k} = 0` for all `0 <= i < L - d`.
+///
+/// Returns the coefficients `F_0, ..., F_d` of up to 64 generators of least
+/// degree.
+fn matrix_generator(sequence: &[[u64; BLOCK]]) -> Vec<Vec<u64>> {
+    // Coefficient bits 0..64 hold F, bits 64..128 hold G. Columns on the G side
+    // start one degree higher so that reductions favour a large F.
+    let mut columns: Vec<Vec<u128>> = (0..2 * BLOCK).map(|j| vec![1u128 << j]).collect();
+    let mut degrees: Vec<usize> = (0..2 * BLOCK).map(|j| (j >= BLOCK) as usize).collect();
+
+    for t in 0..sequence.len() {
+        let mut residuals: Vec<u64> = columns
+            .iter()
+            .map(|column| {
+                let mut r = column.get(t).map_or(0, |&c| (c >> BLOCK) as u64);
+                for (k, &c) in column.iter().enumerate().take(t + 1) {
+                    r ^= mul_small_vector(&sequence[t - k], c as u64);
+                }
+                r
+            })
+            .collect();
+
+        let mut order: Vec<usize> = (0..2 * BLOCK).collect();
+        order.sort_by_key(|&j| degrees[j]);
+        let mut pivots: Vec<(u32, usize)> = Vec::new();
+        for &j in &order {
+            let mut r = residuals[j];
+            for &(position, k) in &pivots {
+                if (r >> position) & 1 == 1 {
+                    r ^= residuals[k];
+                    let pivot = columns[k].clone();
+                    if columns[j].len() < pivot.len() {
+                        columns[j].resize(pivot.len(), 0);
+                    }
+                    for (c, p) in columns[j].iter_mut().zip(pivot) {
+                        *c ^= p;
+                    }
+                }
+            }
+            residuals[j] = r;
+            if r != 0 {
+                pivots.push((r.trailing_zeros(), j));
+            }
+        }
+        for &(_, k) in &pivots {
+            columns[k].insert(0, 0);
+            degrees[k] += 1;
+        }
+    }
+
+    let last_nonzero = |column: &[u128], mask: u128| column.iter().rposition(|&c| c & mask != 0);
+    let low = u64::MAX as u128;
+    let mut generators: Vec<Vec<u64>> = columns
+        .iter()
+        .filter_map(|column| {
+            let f_degree = last_nonzero(column, low)?;
+            match last_nonzero(column, !low) {
+                Some(g_degree) if g_degree >= f_degree => None,
+                _ => Some(column[..=f_degree].iter().map(|&c| c as u64).collect()),
+            }
+        })
+        .collect();
+    generators.sort_by_key(|f| f.len());
+    generators.truncate(BLOCK);
+    generators
+}
+
+/// Finds combinations of the 128 columns of `[low | high]` that `matrix` maps
+/// to zero, and returns a maximal independent set of the nonzero ones.
+fn combine_kernel_vectors<B: GF2BlackBox + ?Sized>(
+    matrix: &B,
+    low: &[u64],
+    high: &[u64],
+) -> Vec<Vec<u8>> {
+    let image_low = matrix.apply(low);
+    let image_high = matrix.apply(high);
+    let rows = image_low
+        .iter()
+        .zip(&image_high)
+        .map(|(&l, &h)| ((h as u128) << BLOCK) | l as u128);
+
+    let n = low.len();
+    let words = n.div_ceil(64);
+    let mut basis: Vec<(usize, Vec<u64>)> = Vec::new();
+    let mut kernel = Vec::new();
+    for combination in row_kernel(rows) {
+        let mut bits = vec![0u64; words];
+        for i in 0..n {
+            let z = ((high[i] as u128) << BLOCK) | low[i] as u128;
+            if (z & combination).count_ones() % 2 == 1 {
+                bits[i / 64] |= 1 << (i % 64);
+            }
+        }
+
+        // Keep the vector only if it is independent of the previous ones.
+        let mut reduced = bits.clone();
+        for (pivot, vector) in &basis {
+            if (reduced[pivot / 64] >> (pivot % 64)) & 1 == 1 {
+                for (r, w) in reduced.iter_mut().zip(vector) {
+                    *r ^= w;
+                }
+            }
+        }
+        let Some(word) = reduced.iter().position(|&w| w != 0) else {
+            continue;
+        };
+
+        let vector: Vec<u64> = (0..n).map(|i| (bits[i / 64] >> (i % 64)) & 1).collect();
+        if matrix.apply(&vector).iter().any(|&w| w != 0) {
+            continue;
+        }
+        basis.push((word * 64 + reduced[word].trailing_zeros() as usize, reduced));
+        kernel.push(vector.into_iter().map(|b| b as u8).collect());
+    }
+    kernel
+}
+
+/// Computes a basis of `{u : r · u = 0 for every row r}` for rows of 128 bits.
+fn row_kernel(rows: impl Iterator<Item = u128>) -> Vec<u128> {
+    // Row echelon basis, sorted by decreasing leading bit.
+    let mut basis: Vec<u128> = Vec::new();
+    for mut row in rows {
+        for &b in &basis {
+            if row & (1 << (127 - b.leading_zeros())) != 0 {
+                row ^= b;
+            }
+        }
+        if row != 0 {
+            let position = basis
+                .iter()
+                .position(|&b| b.leading_zeros() > row.leading_zeros())
+                .unwrap_or(basis.len());
+            basis.insert(position, row);
+        }
+    }
+
+    // Reduce to row-reduced echelon form.
+    for i in 0..basis.len() {
+        let pivot = 1u128 << (127 - basis[i].leading_zeros());
+        for j in 0..basis.len() {
+            if j != i && basis[j] & pivot != 0 {
+                basis[j] ^= basis[i];
+            }
+        }
+    }
+
+    let pivots = basis
+        .iter()
+        .fold(0u128, |acc, &b| acc | (1 << (127 - b.leading_zeros())));
+    (0..128)
+        .filter(|&free| pivots & (1 << free) == 0)
+        .map(|free| {
+            let mut u = 1u128 << free;
+            for &b in &basis {
+                if b & (1 << free) != 0 {
+                    u |= 1 << (127 - b.leading_zeros());
+                }
+            }
+            u
+        })
+        .collect()
+}
+
+/// Selects the columns `S_i` of `V_i^T A V_i` used by the block Lanczos
+/// iteration and computes `W_i^inv = S_i (S_i^T V_i^T A V_i S_i)^{-1} S_i^T`.
+///
+/// Columns not selected in the previous iteration are tried first, since every
+/// column must be selected in at least one of two consecutive iterations.
+fn find_nonsingular_subset(
+    t: &[u64; BLOCK],
+    last_selected: &[usize],
+) -> Option<([u64; BLOCK], Vec<usize>)> {
+    // M = [t | I], rows stored as (left, right).
+    let mut m: Vec<(u64, u64)> = (0..BLOCK).map(|i| (t[i], 1 << i)).collect();
+
+    let last_mask = last_selected.iter().fold(0u64, |acc, &c| acc | (1 << c));
+    let mut order: Vec<usize> = (0..BLOCK).filter(|&c| last_mask & (1 << c) == 0).collect();
+    order.extend(last_selected.iter().rev());
+
+    let mut selected = Vec::new();
+    for i in 0..BLOCK {
+        let column = order[i];
+        let mask = 1u64 << column;
+
+        if let Some(j) = (i..BLOCK).find(|&j| m[order[j]].0 & mask != 0) {
+            m.swap(order[i], order[j]);
+            let pivot = m[column];
+            for &r in &order {
+                if r != column && m[r].0 & mask != 0 {
+                    m[r].0 ^= pivot.0;
+                    m[r].1 ^= pivot.1;
+                }
+            }
+            selected.push(column);
+            continue;
+        }
+
+        // No pivot in the left half: use the right half to compensate and drop
+        // the column.
+        let j = (i..BLOCK).find(|&j| m[order[j]].1 & mask != 0)?;
+        m.swap(order[i], order[j]);
+        let pivot = m[column];
+        for &r in &order {
+            if r != column && m[r].1 & mask != 0 {
+                m[r].0 ^= pivot.0;
+                m[r].1 ^= pivot.1;
+            }
+        }
+        m[column] = (0, 0);
+    }
+
+    let mask = selected.iter().fold(last_mask, |acc, &c| acc | (1 << c));
+    if mask != u64::MAX {
+        return None;
+    }
+
+    let mut winv = [0u64; BLOCK];
+    for (w, row) in winv.iter_mut().zip(&m) {
+        *w = row.1;
+    }
+    Some((winv, selected))
+}
+
+/// Computes the 64 x 64 matrix `V^T W` of two block vectors.
+fn inner_product(v: &[u64], w: &[u64]) -> [u64; BLOCK] {
+    // tables[k][b] accumulates the rows of w whose byte k of v equals b.
+    let mut tables = vec![[0u64; 256]; 8];
+    for (&vi, &wi) in v.iter().zip(w) {
+        for (k, table) in tables.iter_mut().enumerate() {
+            table[((vi >> (8 * k)) & 0xff) as usize] ^= wi;
+        }
+    }
+
+    let mut result = [0u64; BLOCK];
+    for (k, table) in tables.iter().enumerate() {
+        for (byte, &acc) in table.iter().enumerate() {
+            for bit in 0..8 {
+                if (byte >> bit) & 1 == 1 {
+                    result[8 * k + bit] ^= acc;
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Computes the block vector `V M` for a 64 x 64 matrix `M`.
+fn mul_block_small(v: &[u64], m: &[u64; BLOCK]) -> Vec<u64> {
+    // tables[k][b] is the combination of rows 8k..8k+8 of m selected by b.
+    let mut tables = vec![[0u64; 256]; 8];
+    for (k, table) in tables.iter_mut().enumerate() {
+        for byte in 1..256usize {
+            let low = byte & (byte - 1);
+            let bit = (byte ^ low).trailing_zeros() as usize;
+            table[byte] = table[low] ^ m[8 * k + bit];
+        }
+    }
+
+    v.iter()
+        .map(|&vi| {
+            tables.iter().enumerate().fold(0, |acc, (k, table)| {
+                acc ^ table[((vi >> (8 * k)) & 0xff) as usize]
+            })
+        })
+        .collect()
+}
+
+/// Computes the product `A B` of two 64 x 64 matrices.
+fn mul_small(a: &[u64; BLOCK], b: &[u64; BLOCK]) -> [u64; BLOCK] {
+    let mut c = [0u64; BLOCK];
+    for (ci, &ai) in c.iter_mut().zip(a) {
+        let mut bits = ai;
+        while bits != 0 {
+            *ci ^= b[bits.trailing_zeros() as usize];
+            bits &= bits - 1;
+        }
+    }
+    c
+}
+
+/// Computes the product `A f` of a 64 x 64 matrix and a vector of 64 bits.
+fn mul_small_vector(a: &[u64; BLOCK], f: u64) -> u64 {
+    a.iter().enumerate().fold(0, |acc, (r, &row)| {
+        acc | (((row & f).count_ones() as u64 & 1) << r)
+    })
+}
+
+/// Returns the next output of the SplitMix64 sequence whose state is `state`.
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Draws a random block vector with the SplitMix64 generator.
+fn random_block(n: usize, state: &mut u64) -> Vec<u64> {
+    (0..n).map(|_| splitmix64(state)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::MatrixTrait;
+    use crate::test_rng::random_sparse;
+    use crate::GF2Matrix;
+
+    fn assert_kernel_vectors(m: &SparseGF2Matrix, kernel: &[Vec<u8>]) {
+        assert!(!kernel.is_empty());
+        for v in kernel {
+            assert!(v.contains(&1));
+            assert!(m.mul_vector(v).iter().all(|&b| b == 0));
+        }
+        assert_eq!(GF2Matrix::new(kernel.to_vec()).rank(), kernel.len());
+    }
+
+    #[test]
+    fn test_small_block_products() {
+        let mut state = 5;
+        let v = random_block(100, &mut state);
+        let w = random_block(100, &mut state);
+        let m: [u64; BLOCK] = random_block(BLOCK, &mut state).try_into().unwrap();
+
+        let ip = inner_product(&v, &w);
+        for (j, &row) in ip.iter().enumerate() {
+            let expected =
+                v.iter().zip(&w).fold(
+                    0,
+                    |acc, (&vi, &wi)| if (vi >> j) & 1 == 1 { acc ^ wi } else { acc },
+                );
+            assert_eq!(row, expected);
+        }
+
+        let vm = mul_block_small(&v, &m);
+        for (r, &vi) in v.iter().enumerate() {
+            let expected = (0..BLOCK).fold(
+                0,
+                |acc, j| if (vi >> j) & 1 == 1 { acc ^ m[j] } else { acc },
+            );
+            assert_eq!(vm[r], expected);
+        }
+    }
+
+    #[test]
+    fn test_block_lanczos() {
+        for seed in 1..4 {
+            let m = random_sparse(500, 560, 10, seed);
+            let kernel = block_lanczos(&m, seed).unwrap();
+            assert_kernel_vectors(&m, &kernel);
+        }
+    }
+
+    #[test]
+    fn test_block_wiedemann() {
+        for seed in 1..4 {
+            // Wide matrix, padded with zero rows.
+            let m = random_sparse(300, 340, 8, seed);
+            let kernel = block_wiedemann(&m, seed).unwrap();
+            assert_kernel_vectors(&m, &kernel);
+        }
+
+        // Square singular matrix: the last column is never used.
+        let rows = (0..200)
+            .map(|i| vec![i % 199, (3 * i + 1) % 199, (11 * i + 7) % 199])
+            .collect();
+        let m = SparseGF2Matrix::from_rows(200, rows);
+        let kernel = block_wiedemann(&m, 9).unwrap();
+        assert_kernel_vectors(&m, &kernel);
+    }
+}
@@ -0,0 +1,301 @@
+use crate::gf2_poly::Gf2Poly;
+use crate::matrix::Number;
+use crate::packed_gf2_matrix::PackedGF2Matrix;
+use alloc::vec::Vec;
+
+/// A linear feedback shift register over GF(2).
+///
+/// The register is described by its length `L` and its connection polynomial
+/// `C(x) = 1 + c_1 x + ... + c_L x^L`, and generates sequences satisfying
+///
+/// `s_t = c_1 s_{t-1} + c_2 s_{t-2} + ... + c_L s_{t-L}` for every `t >= L`.
+///
+/// The degree of `C(x)` may be smaller than `L`: this happens for sequences
+/// such as `1, 0, 0, 0` whose shortest register has no feedback taps.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lfsr {
+    connection: Gf2Poly,
+    length: usize,
+}
+
+impl Lfsr {
+    /// Creates a new LFSR from its connection polynomial and its length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the constant term of `connection` is not `1`, or if the degree
+    /// of `connection` is larger than `length`.
+    pub fn new(connection: Gf2Poly, length: usize) -> Self {
+        assert_eq!(
+            connection.coefficient(0),
+            1,
+            "connection polynomial must have constant term 1"
+        );
+        assert!(
+            connection.degree().unwrap_or(0) <= length,
+            "connection polynomial degree exceeds the register length"
+        );
+        Self { connection, length }
+    }
+
+    /// Computes the shortest LFSR generating `sequence` with the
+    /// Berlekamp–Massey algorithm.
+    ///
+    /// See [`berlekamp_massey`].
+    pub fn from_sequence(sequence: &[u8]) -> Self {
+        berlekamp_massey(sequence)
+    }
+
+    /// Returns the length `L` of the register, i.e. the linear complexity of the
+    /// sequences it generates.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the connection polynomial `C(x)` of the register.
+    pub fn connection_polynomial(&self) -> &Gf2Poly {
+        &self.connection
+    }
+
+    /// Generates `n` bits of output from the register.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_state` - the first `L` bits `s_0, ..., s_{L-1}` of the sequence.
+    /// * `n` - the number of bits to generate.
+    ///
+    /// # Returns
+    ///
+    /// The bits `s_0, ..., s_{n-1}`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `initial_state.len()` differs from the register length.
+    pub fn generate(&self, initial_state: &[u8], n: usize) -> Vec<u8> {
+        assert_eq!(
+            initial_state.len(),
+            self.length,
+            "initial state must have the register length"
+        );
+
+        let mut sequence = initial_state.to_vec();
+        for t in self.length..n {
+            let mut bit = 0;
+            for i in 1..=self.length {
+                bit ^= self.connection.coefficient(i) & sequence[t - i];
+            }
+            sequence.push(bit);
+        }
+        sequence.truncate(n);
+        sequence
+    }
+
+    /// Returns the companion matrix of the register as a bit-packed matrix.
+    ///
+    /// The state at time `t` is the packed vector `(s_t, s_{t+1}, ..., s_{t+L-1})`
+    /// in MSB order, i.e. `s_t` is stored in column `0`. The companion matrix `M`
+    /// maps the state at time `t` to the state at time `t + 1`:
+    ///
+    /// `M * state_t = state_{t+1}`
+    ///
+    /// so `M^k * state_0` is the state after `k` clocks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the register length is zero or does not fit in `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_poly::Gf2Poly;
+    /// # use lin_algebra::lfsr::Lfsr;
+    /// // s_t = s_{t-1} + s_{t-3}
+    /// let lfsr = Lfsr::new(Gf2Poly::from_coefficients(&[1, 1, 0, 1]), 3);
+    /// let m = lfsr.companion_matrix::<u8>();
+    ///
+    /// // (s_0, s_1, s_2) = (1, 0, 0) is followed by (s_1, s_2, s_3) = (0, 0, 1).
+    /// assert_eq!(m.matrix_by_vector(&0b100u8), 0b001u8);
+    /// ```
+    pub fn companion_matrix<T: Number>(&self) -> PackedGF2Matrix<T> {
+        companion_matrix(&self.connection, self.length)
+    }
+}
+
+/// Computes the shortest LFSR generating a bit sequence over GF(2) using the
+/// Berlekamp–Massey algorithm.
+///
+/// # Arguments
+///
+/// * `sequence` - the bits of the sequence, each `0` or `1`.
+///
+/// # Returns
+///
+/// The shortest [`Lfsr`] whose output starts with `sequence`. Its length is the
+/// linear complexity of the sequence.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::lfsr::berlekamp_massey;
+/// let lfsr = berlekamp_massey(&[1, 0, 0, 1, 1, 1, 0, 1, 0, 0]);
+///
+/// assert_eq!(lfsr.length(), 3);
+/// assert_eq!(lfsr.connection_polynomial().coefficients(), vec![1, 1, 0, 1]);
+/// ```
+pub fn berlekamp_massey(sequence: &[u8]) -> Lfsr {
+    run_berlekamp_massey(sequence, |_| {})
+}
+
+/// Returns the linear complexity of a bit sequence, i.e. the length of the
+/// shortest LFSR generating it.
+pub fn linear_complexity(sequence: &[u8]) -> usize {
+    berlekamp_massey(sequence).length()
+}
+
+/// Computes the linear complexity profile of a bit sequence.
+///
+/// # Returns
+///
+/// A vector whose entry `k` is the linear complexity of the prefix
+/// `s_0, ..., s_k`.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::lfsr::linear_complexity_profile;
+/// assert_eq!(linear_complexity_profile(&[0, 0, 1, 0]), vec![0, 0, 3, 3]);
+/// ```
+pub fn linear_complexity_profile(sequence: &[u8]) -> Vec<usize> {
+    let mut profile = Vec::with_capacity(sequence.len());
+    run_berlekamp_massey(sequence, |l| profile.push(l));
+    profile
+}
+
+/// Returns the companion matrix of the LFSR with connection polynomial
+/// `connection` and length `length`.
+///
+/// See [`Lfsr::companion_matrix`] for the state convention.
+///
+/// # Panics
+///
+/// Panics if `length` is zero or does not fit in `T`, or if the degree of
+/// `connection` exceeds `length`.
+pub fn companion_matrix<T: Number>(connection: &Gf2Poly, length: usize) -> PackedGF2Matrix<T> {
+    assert!(length > 0, "companion matrix needs a non-empty register");
+    assert!(
+        length <= core::mem::size_of::<T>() * 8,
+        "register length {} does not fit in the packed type",
+        length
+    );
+    assert!(
+        connection.degree().unwrap_or(0) <= length,
+        "connection polynomial degree exceeds the register length"
+    );
+
+    // Rows 0..L-1 shift the state, the last row computes the feedback
+    // s_{t+L} = c_1 s_{t+L-1} + ... + c_L s_t.
+    let mut rows = Vec::with_capacity(length);
+    for i in 0..length - 1 {
+        rows.push(T::one() << (length - 2 - i));
+    }
+    let mut feedback = T::zero();
+    for col in 0..length {
+        if connection.coefficient(length - col) == 1 {
+            feedback = feedback ^ (T::one() << (length - 1 - col));
+        }
+    }
+    rows.push(feedback);
+
+    PackedGF2Matrix::new(rows, length)
+}
+
+/// Runs Berlekamp–Massey on `sequence`, reporting the linear complexity after
+/// each processed bit.
+fn run_berlekamp_massey<F: FnMut(usize)>(sequence: &[u8], mut on_step: F) -> Lfsr {
+    let mut connection = Gf2Poly::one();
+    let mut previous = Gf2Poly::one();
+    let mut length = 0;
+    let mut shift = 1;
+
+    for n in 0..sequence.len() {
+        assert!(sequence[n] <= 1, "sequence bits must be 0 or 1");
+
+        let mut discrepancy = sequence[n];
+        for i in 1..=length {
+            discrepancy ^= connection.coefficient(i) & sequence[n - i];
+        }
+
+        if discrepancy == 0 {
+            shift += 1;
+        } else if 2 * length <= n {
+            let old = connection.clone();
+            connection.add_shifted(&previous, shift);
+            length = n + 1 - length;
+            previous = old;
+            shift = 1;
+        } else {
+            connection.add_shifted(&previous, shift);
+            shift += 1;
+        }
+
+        on_step(length);
+    }
+
+    Lfsr { connection, length }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_berlekamp_massey_recovers_register() {
+        // x^5 + x^2 + 1 is primitive, so the sequence has period 31.
+        let lfsr = Lfsr::new(Gf2Poly::from_coefficients(&[1, 0, 0, 1, 0, 1]), 5);
+        let sequence = lfsr.generate(&[1, 0, 0, 1, 0], 62);
+
+        let recovered = berlekamp_massey(&sequence);
+        assert_eq!(recovered, lfsr);
+        assert_eq!(recovered.generate(&sequence[..5], 62), sequence);
+    }
+
+    #[test]
+    fn test_berlekamp_massey_degenerate_sequences() {
+        assert_eq!(linear_complexity(&[]), 0);
+        assert_eq!(linear_complexity(&[0, 0, 0, 0]), 0);
+
+        let lfsr = berlekamp_massey(&[0, 0, 0, 1]);
+        assert_eq!(lfsr.length(), 4);
+        assert_eq!(
+            lfsr.connection_polynomial(),
+            &Gf2Poly::from_coefficients(&[1, 0, 0, 0, 1])
+        );
+
+        let lfsr = berlekamp_massey(&[1, 0, 0, 0]);
+        assert_eq!(lfsr.length(), 1);
+        assert_eq!(lfsr.connection_polynomial(), &Gf2Poly::one());
+    }
+
+    #[test]
+    fn test_linear_complexity_profile() {
+        let sequence = [1, 0, 0, 1, 1, 1, 0, 1, 0, 0];
+        let profile = linear_complexity_profile(&sequence);
+        assert_eq!(profile, vec![1, 1, 1, 3, 3, 3, 3, 3, 3, 3]);
+        assert_eq!(*profile.last().unwrap(), linear_complexity(&sequence));
+    }
+
+    #[test]
+    fn test_companion_matrix_clocks_the_register() {
+        let lfsr = Lfsr::new(Gf2Poly::from_coefficients(&[1, 0, 0, 1, 0, 1]), 5);
+        let sequence = lfsr.generate(&[1, 1, 0, 1, 0], 40);
+        let m = lfsr.companion_matrix::<u32>();
+
+        let pack = |bits: &[u8]| bits.iter().fold(0u32, |acc, &b| (acc << 1) | b as u32);
+
+        let mut state = pack(&sequence[..5]);
+        for t in 1..=35 {
+            state = m.matrix_by_vector(&state);
+            assert_eq!(state, pack(&sequence[t..t + 5]));
+        }
+    }
+}
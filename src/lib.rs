@@ -1,6 +1,33 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod affine_subspace;
+#[cfg(any(feature = "proptest", feature = "quickcheck"))]
+pub mod arbitrary;
+pub mod block;
+pub mod boolean_poly;
+pub mod circulant;
+pub mod column_packed_gf2_matrix;
+pub mod error;
 pub mod gf2_matrix;
+pub mod gf2_poly;
+pub mod gf2_vector;
+pub mod groebner;
+pub mod iterative_solvers;
+pub mod lfsr;
 pub mod matrix;
 pub mod packed_gf2_matrix;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod simd;
+pub mod span;
+pub mod sparse_gf2_matrix;
+pub mod subspace;
+#[cfg(test)]
+mod test_rng;
+pub mod view;
+pub mod xl;
 pub use gf2_matrix::GF2Matrix;
 pub mod convert;
 
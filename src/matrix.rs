@@ -1,8 +1,11 @@
+use alloc::vec::Vec;
+use core::ops::{BitAnd, BitXor, Shl, Shr};
 use num_traits::{One, Zero};
-use std::ops::{BitAnd, BitXor, Shl, Shr};
 
 pub trait Number:
     Copy
+    + Send
+    + Sync
     + Eq
     + Ord
     + BitXor<Output = Self>
@@ -12,28 +15,59 @@ pub trait Number:
     + Zero
     + One
 {
+    /// Converts to `usize`, truncating the bits that do not fit.
     fn into_usize(self) -> usize;
+    /// Returns the number of bits set to one.
+    fn count_ones(self) -> u32;
 }
 
 impl Number for u8 {
     fn into_usize(self) -> usize {
         self as usize
     }
+    fn count_ones(self) -> u32 {
+        u8::count_ones(self)
+    }
 }
 impl Number for u16 {
     fn into_usize(self) -> usize {
         self as usize
     }
+    fn count_ones(self) -> u32 {
+        u16::count_ones(self)
+    }
 }
 impl Number for u32 {
     fn into_usize(self) -> usize {
         self as usize
     }
+    fn count_ones(self) -> u32 {
+        u32::count_ones(self)
+    }
 }
 impl Number for u64 {
     fn into_usize(self) -> usize {
         self as usize
     }
+    fn count_ones(self) -> u32 {
+        u64::count_ones(self)
+    }
+}
+impl Number for u128 {
+    fn into_usize(self) -> usize {
+        self as usize
+    }
+    fn count_ones(self) -> u32 {
+        u128::count_ones(self)
+    }
+}
+impl Number for usize {
+    fn into_usize(self) -> usize {
+        self
+    }
+    fn count_ones(self) -> u32 {
+        usize::count_ones(self)
+    }
 }
 
 pub trait MatrixTrait<T: Number>: MatrixCommon<T> {
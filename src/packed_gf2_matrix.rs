@@ -1,4 +1,25 @@
-use crate::{matrix::Number, GF2Matrix};
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{
+    column_packed_gf2_matrix::transpose_rows,
+    gf2_poly::Gf2Poly,
+    matrix::Number,
+    view::{GF2Storage, GF2StorageMut},
+    GF2Matrix,
+};
+
+/// Number of rows from which elimination and products are split across
+/// threads with the `rayon` feature.
+#[cfg(feature = "rayon")]
+const PARALLEL_MIN_ROWS: usize = 1 << 12;
+
+/// Number of rows handled by one task of a parallel elimination step.
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_ROWS: usize = 1 << 10;
 
 #[derive(Clone, Copy, Debug)]
 pub enum BitOrder {
@@ -14,14 +35,13 @@ pub enum BitOrder {
 ///
 /// This type is typically used as a compact or efficient
 /// representation before expanding into an explicit GF(2) matrix.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PackedGF2Matrix<T: Number> {
     elements: Vec<T>,
     n: usize,
 }
 
 impl<T: Number> PackedGF2Matrix<T> {
-    
     /// Creates a new integer-encoded matrix.
     ///
     /// # Arguments
@@ -44,7 +64,7 @@ impl<T: Number> PackedGF2Matrix<T> {
     }
 
     fn get_packed_bit(value: T, len: usize, idx: usize) -> u8 {
-        ((value.into_usize() >> (len - 1 - idx)) & 1) as u8
+        ((value >> (len - 1 - idx)) & T::one() != T::zero()) as u8
     }
 
     fn toggle_packed_bit(value: &mut T, len: usize, idx: usize) {
@@ -203,7 +223,7 @@ impl<T: Number> PackedGF2Matrix<T> {
     }
 
     fn get_element(&self, row: usize, col: usize) -> u8 {
-        ((self.elements[row].into_usize() >> (self.ncols() - 1 - col)) & 1) as u8
+        Self::get_packed_bit(self.elements[row], self.ncols(), col)
     }
 
     fn swap_rows(&mut self, idx1: usize, idx2: usize) {
@@ -323,17 +343,52 @@ impl<T: Number> PackedGF2Matrix<T> {
                 operations.push((r, i));
             }
 
-            for i in 0..self.nrows() {
-                if i != r && self.get_element(i, lead) == 1 {
-                    self.elements[i] = self.elements[i] ^ self.elements[r];
-                    operations.push((i, r));
-                }
-            }
+            self.eliminate_column(r, lead, operations);
 
             lead += 1;
         }
     }
 
+    /// Adds the pivot row `r` to every other row with a one in column `lead`,
+    /// recording the operations in increasing row order.
+    ///
+    /// With the `rayon` feature, large matrices are split in chunks of rows
+    /// updated in parallel; the result and the recorded operations are the same
+    /// as in the serial path.
+    fn eliminate_column(&mut self, r: usize, lead: usize, operations: &mut Vec<(usize, usize)>) {
+        let pivot_row = self.elements[r];
+        let bit = T::one() << (self.ncols() - 1 - lead);
+
+        #[cfg(feature = "rayon")]
+        if self.nrows() >= PARALLEL_MIN_ROWS {
+            let updated: Vec<Vec<usize>> = self
+                .elements
+                .par_chunks_mut(PARALLEL_CHUNK_ROWS)
+                .enumerate()
+                .map(|(chunk_index, chunk)| {
+                    let mut updated = Vec::new();
+                    for (k, row) in chunk.iter_mut().enumerate() {
+                        let i = chunk_index * PARALLEL_CHUNK_ROWS + k;
+                        if i != r && *row & bit != T::zero() {
+                            *row = *row ^ pivot_row;
+                            updated.push(i);
+                        }
+                    }
+                    updated
+                })
+                .collect();
+            operations.extend(updated.into_iter().flatten().map(|i| (i, r)));
+            return;
+        }
+
+        for (i, row) in self.elements.iter_mut().enumerate() {
+            if i != r && *row & bit != T::zero() {
+                *row = *row ^ pivot_row;
+                operations.push((i, r));
+            }
+        }
+    }
+
     /// Converts this matrix into row-reduced echelon form over GF(2).
     ///
     /// This method consumes the matrix, performs Gauss-Jordan-style elimination
@@ -449,6 +504,10 @@ impl<T: Number> PackedGF2Matrix<T> {
                         }
                     }
 
+                    if (0..self.nrows()).any(|i| i != row && self.get_element(i, pivot) == 1) {
+                        return false;
+                    }
+
                     previous_pivot = Some(pivot);
                 }
             }
@@ -700,6 +759,10 @@ impl<T: Number> PackedGF2Matrix<T> {
     /// value of type `T`.
     ///
     /// The returned packed vector has length `self.nrows()`.
+    ///
+    /// To access every column, [`Self::transpose`] or
+    /// [`ColumnPackedGF2Matrix`](crate::column_packed_gf2_matrix::ColumnPackedGF2Matrix)
+    /// extract them all at once.
     pub fn column_packed(&self, idx: usize) -> T {
         assert!(
             idx < self.ncols(),
@@ -719,6 +782,479 @@ impl<T: Number> PackedGF2Matrix<T> {
         column
     }
 
+    /// Multiplies the bit-packed matrix by a bit-packed column vector over GF(2).
+    ///
+    /// The vector `v` is interpreted as having length `self.ncols()`, and entry
+    /// `i` of the result is the parity of `row_i & v`. The returned vector is
+    /// packed in MSB order with length `self.nrows()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::packed_gf2_matrix::PackedGF2Matrix;
+    /// let m = PackedGF2Matrix::new(vec![0b110u8, 0b011u8], 3);
+    ///
+    /// // [1 1 0] * (1, 0, 1) = 1, [0 1 1] * (1, 0, 1) = 1
+    /// assert_eq!(m.matrix_by_vector(&0b101u8), 0b11u8);
+    /// ```
+    pub fn matrix_by_vector(&self, v: &T) -> T {
+        let mut result = T::zero();
+
+        for (i, &row) in self.elements.iter().enumerate() {
+            if (row & *v).count_ones() % 2 == 1 {
+                Self::toggle_packed_bit(&mut result, self.nrows(), i);
+            }
+        }
+
+        result
+    }
+
+    /// Returns the `n x n` identity matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` columns do not fit in `T`.
+    pub fn identity(n: usize) -> Self {
+        assert!(
+            n <= core::mem::size_of::<T>() * 8,
+            "{} columns do not fit in the packed type",
+            n
+        );
+        let elements = (0..n).map(|i| T::one() << (n - 1 - i)).collect();
+        Self::new(elements, n)
+    }
+
+    /// Multiplies this matrix by `other` over GF(2).
+    ///
+    /// Row `i` of the product is the XOR of the rows of `other` selected by the
+    /// set bits of row `i` of `self`.
+    ///
+    /// If `self` has shape `m x n` and `other` has shape `n x k`, the product has
+    /// shape `m x k`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self.ncols() != other.nrows()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::packed_gf2_matrix::PackedGF2Matrix;
+    /// let a = PackedGF2Matrix::new(vec![0b11u8, 0b01u8], 2);
+    /// let b = PackedGF2Matrix::new(vec![0b10u8, 0b11u8], 2);
+    ///
+    /// assert_eq!(a.mul(&b), PackedGF2Matrix::new(vec![0b01u8, 0b11u8], 2));
+    /// ```
+    pub fn mul(&self, other: &PackedGF2Matrix<T>) -> PackedGF2Matrix<T> {
+        assert_eq!(
+            self.ncols(),
+            other.nrows(),
+            "left-hand side columns must match right-hand side rows"
+        );
+
+        let product_row = |&row: &T| {
+            let mut acc = T::zero();
+            for (j, &other_row) in other.elements.iter().enumerate() {
+                if Self::get_packed_bit(row, self.ncols(), j) == 1 {
+                    acc = acc ^ other_row;
+                }
+            }
+            acc
+        };
+
+        #[cfg(feature = "rayon")]
+        let elements = if self.nrows() >= PARALLEL_MIN_ROWS {
+            self.elements.par_iter().map(product_row).collect()
+        } else {
+            self.elements.iter().map(product_row).collect()
+        };
+        #[cfg(not(feature = "rayon"))]
+        let elements = self.elements.iter().map(product_row).collect();
+
+        PackedGF2Matrix::new(elements, other.ncols())
+    }
+
+    /// Returns the transpose of this matrix.
+    ///
+    /// The rows are transposed by blocks of `w x w` bits, for words of `w`
+    /// bits, in `O(nrows * log w)` word operations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows of the transpose, of `self.nrows()` bits, do not fit
+    /// in `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::packed_gf2_matrix::PackedGF2Matrix;
+    /// let m = PackedGF2Matrix::new(vec![0b110u8, 0b011u8], 3);
+    ///
+    /// assert_eq!(m.transpose(), PackedGF2Matrix::new(vec![0b10, 0b11, 0b01], 2));
+    /// ```
+    pub fn transpose(&self) -> PackedGF2Matrix<T> {
+        let width = core::mem::size_of::<T>() * 8;
+        assert!(
+            self.nrows() <= width,
+            "{} rows do not fit in the packed type",
+            self.nrows()
+        );
+        if self.nrows() == 0 {
+            return PackedGF2Matrix::new(vec![T::zero(); self.ncols()], 0);
+        }
+
+        // A single block: each column comes out in one word, aligned on its
+        // most significant bit.
+        let columns = transpose_rows(&self.elements, self.ncols());
+        let elements = columns
+            .into_iter()
+            .map(|column| column >> (width - self.nrows()))
+            .collect();
+        PackedGF2Matrix::new(elements, self.nrows())
+    }
+
+    /// Returns the product `self * other^T`.
+    ///
+    /// Entry `(i, j)` is the parity of the rows `i` of `self` and `j` of
+    /// `other`, so no transpose is computed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrices do not have the same number of columns, or if
+    /// the rows of the product, of `other.nrows()` bits, do not fit in `T`.
+    pub fn mul_transpose(&self, other: &PackedGF2Matrix<T>) -> PackedGF2Matrix<T> {
+        assert_eq!(
+            self.ncols(),
+            other.ncols(),
+            "both operands must have the same number of columns"
+        );
+        assert!(
+            other.nrows() <= core::mem::size_of::<T>() * 8,
+            "{} columns do not fit in the packed type",
+            other.nrows()
+        );
+
+        let product_row = |&row: &T| {
+            other.elements.iter().fold(T::zero(), |acc, &other_row| {
+                let parity = if (row & other_row).count_ones() % 2 == 1 {
+                    T::one()
+                } else {
+                    T::zero()
+                };
+                (acc << 1) ^ parity
+            })
+        };
+
+        #[cfg(feature = "rayon")]
+        let elements = if self.nrows() >= PARALLEL_MIN_ROWS {
+            self.elements.par_iter().map(product_row).collect()
+        } else {
+            self.elements.iter().map(product_row).collect()
+        };
+        #[cfg(not(feature = "rayon"))]
+        let elements = self.elements.iter().map(product_row).collect();
+
+        PackedGF2Matrix::new(elements, other.nrows())
+    }
+
+    /// Returns the product `self^T * other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrices do not have the same number of rows, or if the
+    /// rows of `self^T` do not fit in `T`.
+    pub fn transpose_mul(&self, other: &PackedGF2Matrix<T>) -> PackedGF2Matrix<T> {
+        assert_eq!(
+            self.nrows(),
+            other.nrows(),
+            "both operands must have the same number of rows"
+        );
+        self.transpose().mul(other)
+    }
+
+    /// Raises this square matrix to the power `exponent` using square-and-multiply.
+    ///
+    /// This needs `O(log(exponent))` matrix products, so jumping a linear state
+    /// machine `2^40` steps ahead costs about 80 products.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::packed_gf2_matrix::PackedGF2Matrix;
+    /// // Cyclic shift of 3 bits has order 3.
+    /// let m = PackedGF2Matrix::new(vec![0b010u8, 0b001u8, 0b100u8], 3);
+    ///
+    /// assert_eq!(m.pow(3), PackedGF2Matrix::identity(3));
+    /// ```
+    pub fn pow(&self, exponent: u64) -> PackedGF2Matrix<T> {
+        assert_eq!(self.nrows(), self.ncols(), "matrix must be square");
+
+        let mut result = Self::identity(self.ncols());
+        let mut base = self.clone();
+        let mut e = exponent;
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result.mul(&base);
+            }
+            e >>= 1;
+            if e > 0 {
+                base = base.mul(&base);
+            }
+        }
+        result
+    }
+
+    /// Advances a linear state machine by `steps` transitions.
+    ///
+    /// This matrix is interpreted as the transition matrix `M` of the state
+    /// machine, and the result is `M^steps * state`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    pub fn jump_ahead(&self, state: &T, steps: u64) -> T {
+        self.pow(steps).matrix_by_vector(state)
+    }
+
+    /// Computes the minimal polynomial of the packed vector `v` with respect to
+    /// this square matrix.
+    ///
+    /// This is the monic polynomial `p` of least degree such that `p(M) * v = 0`.
+    /// It is found from the first linear dependency in the Krylov sequence
+    /// `v, M v, M^2 v, ...`, and divides the minimal polynomial of `M`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    pub fn minimal_polynomial_of_vector(&self, v: &T) -> Gf2Poly {
+        self.krylov_dependency(v).1
+    }
+
+    /// Advances a linear state machine by `steps` transitions using the minimal
+    /// polynomial of the state.
+    ///
+    /// With `p` the minimal polynomial of `state` (see
+    /// [`Self::minimal_polynomial_of_vector`]), `M^steps * state` equals
+    /// `r(M) * state` where `r = x^steps mod p`. Only `deg(p)` matrix-vector
+    /// products and `O(log(steps))` polynomial products modulo `p` are needed,
+    /// which makes this the preferred method for very large exponents.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::packed_gf2_matrix::PackedGF2Matrix;
+    /// let m = PackedGF2Matrix::new(vec![0b010u8, 0b001u8, 0b101u8], 3);
+    ///
+    /// assert_eq!(m.jump_ahead_large(&0b100u8, 1 << 100), m.jump_ahead(&0b100u8, 2));
+    /// ```
+    pub fn jump_ahead_large(&self, state: &T, steps: u128) -> T {
+        let (krylov, p) = self.krylov_dependency(state);
+        let r = Gf2Poly::monomial(1).pow_mod(steps, &p);
+
+        let mut result = T::zero();
+        for (i, &v) in krylov.iter().enumerate() {
+            if r.coefficient(i) == 1 {
+                result = result ^ v;
+            }
+        }
+        result
+    }
+
+    /// Computes the characteristic polynomial `det(x I - M)` of this square
+    /// matrix.
+    ///
+    /// The matrix is first brought to upper Hessenberg form by similarity
+    /// transformations (which preserve the characteristic polynomial), and the
+    /// polynomial is then obtained from the Hessenberg recurrence on its leading
+    /// principal submatrices. Irreducible factors are available through
+    /// [`Gf2Poly::factor`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_poly::Gf2Poly;
+    /// # use lin_algebra::packed_gf2_matrix::PackedGF2Matrix;
+    /// let m = PackedGF2Matrix::new(vec![0b010u8, 0b001u8, 0b110u8], 3);
+    ///
+    /// // Companion matrix of x^3 + x + 1.
+    /// assert_eq!(
+    ///     m.characteristic_polynomial(),
+    ///     Gf2Poly::from_coefficients(&[1, 1, 0, 1])
+    /// );
+    /// ```
+    pub fn characteristic_polynomial(&self) -> Gf2Poly {
+        assert_eq!(self.nrows(), self.ncols(), "matrix must be square");
+
+        let n = self.ncols();
+        let h = self.hessenberg_form();
+        let entry = |r: usize, c: usize| Self::get_packed_bit(h[r], n, c) == 1;
+
+        // p[m] is the characteristic polynomial of the leading m x m block.
+        let mut p: Vec<Gf2Poly> = Vec::with_capacity(n + 1);
+        p.push(Gf2Poly::one());
+        for m in 1..=n {
+            let mut next = Gf2Poly::zero();
+            next.add_shifted(&p[m - 1], 1);
+            if entry(m - 1, m - 1) {
+                next.add_shifted(&p[m - 1], 0);
+            }
+            for i in (1..m).rev() {
+                if !entry(i, i - 1) {
+                    break;
+                }
+                if entry(i - 1, m - 1) {
+                    next.add_shifted(&p[i - 1], 0);
+                }
+            }
+            p.push(next);
+        }
+
+        p.pop().unwrap()
+    }
+
+    /// Computes the minimal polynomial of this square matrix, i.e. the monic
+    /// polynomial `p` of least degree such that `p(M) = 0`.
+    ///
+    /// It is the least common multiple of the minimal polynomials of the unit
+    /// vectors, each obtained from its Krylov sequence.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    pub fn minimal_polynomial(&self) -> Gf2Poly {
+        assert_eq!(self.nrows(), self.ncols(), "matrix must be square");
+
+        let n = self.ncols();
+        let mut minimal = Gf2Poly::one();
+        for i in 0..n {
+            let mut unit = T::zero();
+            Self::toggle_packed_bit(&mut unit, n, i);
+            minimal = minimal.lcm(&self.minimal_polynomial_of_vector(&unit));
+        }
+        minimal
+    }
+
+    /// Returns the order of this square matrix, i.e. the least `k >= 1` such
+    /// that `M^k = I`.
+    ///
+    /// The order is computed as the order of the minimal polynomial (see
+    /// [`Gf2Poly::order`]), so no matrix powers are formed.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the matrix is singular.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::packed_gf2_matrix::PackedGF2Matrix;
+    /// let m = PackedGF2Matrix::new(vec![0b010u8, 0b001u8, 0b100u8], 3);
+    ///
+    /// assert_eq!(m.order(), Some(3));
+    /// ```
+    pub fn order(&self) -> Option<u128> {
+        self.minimal_polynomial().order()
+    }
+
+    /// Returns the rows of a matrix similar to this square matrix and in upper
+    /// Hessenberg form (zero below the first subdiagonal).
+    fn hessenberg_form(&self) -> Vec<T> {
+        let n = self.ncols();
+        let mut h = self.elements.clone();
+        let get = |h: &[T], r: usize, c: usize| Self::get_packed_bit(h[r], n, c) == 1;
+
+        for j in 0..n.saturating_sub(2) {
+            let pivot = match (j + 1..n).find(|&i| get(&h, i, j)) {
+                Some(pivot) => pivot,
+                None => continue,
+            };
+
+            // Similarity by a permutation: swap rows and columns.
+            if pivot != j + 1 {
+                h.swap(pivot, j + 1);
+                for row in h.iter_mut() {
+                    if Self::get_packed_bit(*row, n, pivot) != Self::get_packed_bit(*row, n, j + 1)
+                    {
+                        Self::toggle_packed_bit(row, n, pivot);
+                        Self::toggle_packed_bit(row, n, j + 1);
+                    }
+                }
+            }
+
+            // Similarity by I + e_k e_(j+1)^T: add row j+1 to row k, then
+            // column k to column j+1.
+            for k in j + 2..n {
+                if get(&h, k, j) {
+                    h[k] = h[k] ^ h[j + 1];
+                    for row in h.iter_mut() {
+                        if Self::get_packed_bit(*row, n, k) == 1 {
+                            Self::toggle_packed_bit(row, n, j + 1);
+                        }
+                    }
+                }
+            }
+        }
+        h
+    }
+
+    /// Returns the Krylov vectors `v, M v, ..., M^(d-1) v` and the minimal
+    /// polynomial of `v`, where `d` is its degree.
+    fn krylov_dependency(&self, v: &T) -> (Vec<T>, Gf2Poly) {
+        assert_eq!(self.nrows(), self.ncols(), "matrix must be square");
+
+        let n = self.ncols();
+        // Reduced Krylov vectors indexed by pivot column, together with the
+        // combination of Krylov vectors they were reduced from.
+        let mut reduced: Vec<Option<(T, Gf2Poly)>> = vec![None; n];
+        let mut krylov = Vec::new();
+        let mut current = *v;
+
+        loop {
+            let mut vector = current;
+            let mut combination = Gf2Poly::monomial(krylov.len());
+
+            let mut free_pivot = None;
+            while let Some(pivot) = (0..n).find(|&c| Self::get_packed_bit(vector, n, c) == 1) {
+                match &reduced[pivot] {
+                    Some((basis_vector, basis_combination)) => {
+                        vector = vector ^ *basis_vector;
+                        combination.add_shifted(basis_combination, 0);
+                    }
+                    None => {
+                        free_pivot = Some(pivot);
+                        break;
+                    }
+                }
+            }
+
+            match free_pivot {
+                // The new Krylov vector reduced to zero: `combination` is the
+                // first linear dependency.
+                None => return (krylov, combination),
+                Some(pivot) => reduced[pivot] = Some((vector, combination)),
+            }
+
+            krylov.push(current);
+            current = self.matrix_by_vector(&current);
+        }
+    }
+
     /// Solves the linear system `A * x = b` over GF(2), where `A` is this
     /// bit-packed matrix and `b` is a packed right-hand side vector.
     ///
@@ -787,12 +1323,12 @@ impl<T: Number> PackedGF2Matrix<T> {
         }
 
         let n_rows = self.ncols(); // rows of X
-        let n_cols = y.ncols(); // columns of X
 
-        let mut solution_rows = vec![T::zero(); n_rows];
+        // The columns of `Y` are the rows of its transpose.
+        let rhs_cols = y.transpose();
 
-        for col in 0..n_cols {
-            let rhs_col = y.column_packed(col);
+        let solve_column = |col: usize| {
+            let rhs_col = rhs_cols.row(col);
 
             let solved_col = Self::apply_operations_packed(&operations, rhs_col, self.nrows());
 
@@ -800,16 +1336,58 @@ impl<T: Number> PackedGF2Matrix<T> {
                 panic!("Linear system is inconsistent");
             }
 
-            let solution_col = Self::truncate_packed_prefix(solved_col, self.nrows(), self.ncols());
+            Self::truncate_packed_prefix(solved_col, self.nrows(), self.ncols())
+        };
 
-            for row in 0..n_rows {
-                if Self::get_packed_bit(solution_col, n_rows, row) == 1 {
-                    Self::toggle_packed_bit(&mut solution_rows[row], n_cols, col);
-                }
-            }
+        // The columns of `Y` are independent right-hand sides. The number of
+        // rows is bounded by the width of `T`, so the work of a column is the
+        // replay of the recorded operations.
+        #[cfg(feature = "rayon")]
+        let solution_cols: Vec<T> = if operations.len() >= PARALLEL_MIN_ROWS {
+            (0..y.ncols()).into_par_iter().map(solve_column).collect()
+        } else {
+            (0..y.ncols()).map(solve_column).collect()
+        };
+        #[cfg(not(feature = "rayon"))]
+        let solution_cols: Vec<T> = (0..y.ncols()).map(solve_column).collect();
+
+        PackedGF2Matrix::new(solution_cols, n_rows).transpose()
+    }
+
+    /// Returns the word with the bits of the columns `cols` set.
+    fn column_mask(&self, cols: Range<usize>) -> T {
+        cols.fold(T::zero(), |mask, col| {
+            mask ^ (T::one() << (self.ncols() - 1 - col))
+        })
+    }
+}
+
+impl<T: Number> GF2Storage for PackedGF2Matrix<T> {
+    fn shape(&self) -> (usize, usize) {
+        (self.nrows(), self.ncols())
+    }
+
+    fn get(&self, row: usize, col: usize) -> u8 {
+        Self::get_packed_bit(self.elements[row], self.n, col)
+    }
+}
+
+impl<T: Number> GF2StorageMut for PackedGF2Matrix<T> {
+    fn set(&mut self, row: usize, col: usize, value: u8) {
+        if GF2Storage::get(self, row, col) != value {
+            Self::toggle_packed_bit(&mut self.elements[row], self.n, col);
         }
+    }
 
-        PackedGF2Matrix::new(solution_rows, n_cols)
+    fn swap_row_segments(&mut self, a: usize, b: usize, cols: Range<usize>) {
+        let diff = (self.elements[a] ^ self.elements[b]) & self.column_mask(cols);
+        self.elements[a] = self.elements[a] ^ diff;
+        self.elements[b] = self.elements[b] ^ diff;
+    }
+
+    fn add_row_segment(&mut self, target: usize, source: usize, cols: Range<usize>) {
+        let segment = self.elements[source] & self.column_mask(cols);
+        self.elements[target] = self.elements[target] ^ segment;
     }
 }
 
@@ -817,6 +1395,8 @@ impl<T: Number> PackedGF2Matrix<T> {
 mod tests {
 
     use super::*;
+    use crate::matrix::MatrixTrait;
+    use crate::test_rng::{splitmix64, xorshift64};
 
     #[test]
     fn test_from_int_matrix_to_gf2_matrix_u8_lsb() {
@@ -871,4 +1451,244 @@ mod tests {
         ];
         assert_eq!(gf2_matrix.elements, expected);
     }
+
+    #[test]
+    fn test_is_reduced_echelon_requires_cleared_pivot_columns() {
+        let echelon = PackedGF2Matrix::<u8>::new(vec![0b11, 0b01], 2);
+        assert!(!echelon.is_reduced_echelon());
+        assert_eq!(echelon.image(), vec![0b10, 0b01]);
+        assert!(PackedGF2Matrix::<u8>::new(vec![0b101, 0b011, 0], 3).is_reduced_echelon());
+    }
+
+    #[test]
+    fn test_large_matrices_match_dense_results() {
+        // Tall enough to take the parallel path with the `rayon` feature.
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let mut next = || xorshift64(&mut state);
+        let a = PackedGF2Matrix::new((0..5000).map(|_| next() >> 24).collect::<Vec<u64>>(), 40);
+        let dense = GF2Matrix::from(&a);
+
+        let (echelon, operations) = a.echelon_form();
+        let (dense_echelon, dense_operations) = dense.echelon_form();
+        assert_eq!(operations, dense_operations);
+        assert_eq!(GF2Matrix::from(&echelon).elements, dense_echelon.elements);
+
+        let b = PackedGF2Matrix::new((0..40).map(|_| next() >> 40).collect::<Vec<u64>>(), 24);
+        let product = GF2Matrix::from(&a.mul(&b));
+        for (i, row) in product.elements.iter().enumerate().step_by(97) {
+            for (j, &x) in row.iter().enumerate() {
+                let dot = (0..40).fold(0, |acc, k| {
+                    acc ^ (dense.elements[i][k] & ((b.row(k) >> (23 - j)) as u8 & 1))
+                });
+                assert_eq!(x, dot);
+            }
+        }
+
+        let x = PackedGF2Matrix::new((0..40).map(|_| next() >> 32).collect::<Vec<u64>>(), 32);
+        // Right-hand side columns are packed in one word, so keep 64 equations.
+        let system = a.window(0..64, 0..40).to_packed::<u64>();
+        assert_eq!(system.solve_matrix_system(&system.mul(&x)), x);
+    }
+
+    #[test]
+    fn test_u128_matrices_match_dense_results() {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = || splitmix64(&mut state);
+        let mut word = || (u128::from(next()) << 64) | u128::from(next());
+
+        // The highest column is bit 127: it must not be lost to truncation.
+        let a = PackedGF2Matrix::new((0..128).map(|_| word()).collect::<Vec<u128>>(), 128);
+        let dense = GF2Matrix::from(&a);
+        assert_eq!(a.get(0, 0), (a.row(0) >> 127) as u8);
+        assert_eq!(dense.view().to_packed::<u128>(), a);
+
+        let (echelon, operations) = a.echelon_form();
+        let (dense_echelon, dense_operations) = dense.echelon_form();
+        assert_eq!(operations, dense_operations);
+        assert_eq!(GF2Matrix::from(&echelon).elements, dense_echelon.elements);
+        assert_eq!(a.rank(), dense.rank());
+        assert_eq!(a.kernel().len(), dense.kernel().len());
+        for k in a.kernel() {
+            assert_eq!(a.matrix_by_vector(&k), 0);
+        }
+        assert_eq!(
+            GF2Matrix::from(&PackedGF2Matrix::new(a.image(), 128)).elements,
+            dense.image()
+        );
+
+        // A tall system of full column rank, so that `solve` recovers `x`.
+        let tall = PackedGF2Matrix::new((0..128).map(|_| word() >> 8).collect::<Vec<u128>>(), 120);
+        assert_eq!(tall.rank(), 120);
+        let x = word() >> 8;
+        assert_eq!(tall.solve(tall.matrix_by_vector(&x)), x);
+    }
+
+    #[test]
+    fn test_usize_matrices() {
+        let width = usize::BITS as usize;
+        let m = PackedGF2Matrix::new(vec![usize::MAX, 1, 1 << (width - 1)], width);
+        assert_eq!(m.rank(), 3);
+        assert_eq!(m.kernel().len(), width - 3);
+        assert_eq!(GF2Matrix::from(&m).elements[2][0], 1);
+    }
+
+    #[test]
+    fn test_transpose_and_transpose_products() {
+        let a = PackedGF2Matrix::<u32>::new(
+            (0..27u32)
+                .map(|i| i.wrapping_mul(0x9e37_79b9) >> 9)
+                .collect(),
+            23,
+        );
+        let t = a.transpose();
+        assert_eq!((t.nrows(), t.ncols()), (23, 27));
+        for i in 0..27 {
+            for j in 0..23 {
+                assert_eq!(t.get(j, i), a.get(i, j));
+            }
+        }
+        assert_eq!(t.transpose(), a);
+        for col in 0..23 {
+            assert_eq!(t.row(col), a.column_packed(col));
+        }
+
+        let b =
+            PackedGF2Matrix::<u32>::new((0..30u32).map(|i| (i * 0x2f1) & 0x7f_ffff).collect(), 23);
+        assert_eq!(a.mul_transpose(&b), a.mul(&b.transpose()));
+        let c = PackedGF2Matrix::<u32>::new((0..27u32).map(|i| i * 0x51).collect(), 12);
+        assert_eq!(a.transpose_mul(&c), t.mul(&c));
+
+        let empty = PackedGF2Matrix::<u8>::new(vec![], 5);
+        assert_eq!(empty.transpose(), PackedGF2Matrix::new(vec![0; 5], 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "10 columns do not fit in the packed type")]
+    fn test_mul_transpose_rejects_too_many_columns() {
+        let a = PackedGF2Matrix::new(vec![0b111u8, 0b101], 3);
+        let b = PackedGF2Matrix::new(vec![0b111u8; 10], 3);
+        a.mul_transpose(&b);
+    }
+    #[test]
+    fn test_mul_and_pow() {
+        let m = PackedGF2Matrix::<u8>::new(vec![0b011, 0b110, 0b101], 3);
+        assert_eq!(m.pow(0), PackedGF2Matrix::identity(3));
+        assert_eq!(m.pow(1), m);
+        assert_eq!(m.pow(2), m.mul(&m));
+        assert_eq!(m.pow(5), m.pow(2).mul(&m.pow(3)));
+
+        let rect = PackedGF2Matrix::<u8>::new(vec![0b10, 0b01, 0b11], 2);
+        assert_eq!(
+            rect.mul(&PackedGF2Matrix::new(vec![0b101, 0b011], 3)),
+            PackedGF2Matrix::new(vec![0b101, 0b011, 0b110], 3)
+        );
+    }
+
+    #[test]
+    fn test_jump_ahead() {
+        // Transition matrix of an xorshift-like map on 16 bits.
+        let mut rows = Vec::new();
+        for i in 0..16 {
+            let mut row = 1u16 << (15 - i);
+            if i >= 3 {
+                row ^= 1 << (15 - (i - 3));
+            }
+            if i + 5 < 16 {
+                row ^= 1 << (15 - (i + 5));
+            }
+            rows.push(row);
+        }
+        let m = PackedGF2Matrix::new(rows, 16);
+
+        let mut state = 0xACE1u16;
+        for _ in 0..1000 {
+            state = m.matrix_by_vector(&state);
+        }
+        assert_eq!(m.jump_ahead(&0xACE1, 1000), state);
+        assert_eq!(m.jump_ahead_large(&0xACE1, 1000), state);
+
+        let steps = (1u64 << 40) + 12345;
+        assert_eq!(
+            m.jump_ahead_large(&0xACE1, steps as u128),
+            m.jump_ahead(&0xACE1, steps)
+        );
+    }
+
+    #[test]
+    fn test_minimal_polynomial_of_vector() {
+        // Companion matrix of 1 + x^2 + x^5: every nonzero state has minimal
+        // polynomial x^5 + x^3 + 1 (the reciprocal of the connection polynomial).
+        let lfsr = crate::lfsr::Lfsr::new(Gf2Poly::from_coefficients(&[1, 0, 1, 0, 0, 1]), 5);
+        let m = lfsr.companion_matrix::<u8>();
+        assert_eq!(
+            m.minimal_polynomial_of_vector(&0b10110),
+            Gf2Poly::from_coefficients(&[1, 0, 0, 1, 0, 1])
+        );
+        assert_eq!(m.minimal_polynomial_of_vector(&0), Gf2Poly::one());
+        assert_eq!(m.jump_ahead_large(&0b10110, 31), 0b10110);
+    }
+
+    #[test]
+    fn test_characteristic_and_minimal_polynomial() {
+        let m = PackedGF2Matrix::<u8>::new(vec![0b1101, 0b0110, 0b1011, 0b0011], 4);
+        let chi = m.characteristic_polynomial();
+        assert_eq!(chi.degree(), Some(4));
+
+        // Cayley–Hamilton: chi(M) = 0, and the minimal polynomial divides chi.
+        let evaluate = |p: &Gf2Poly| {
+            let mut acc = PackedGF2Matrix::new(vec![0; 4], 4);
+            for (i, &c) in p.coefficients().iter().enumerate() {
+                if c == 1 {
+                    let power = m.pow(i as u64);
+                    acc = PackedGF2Matrix::new(
+                        (0..4).map(|r| acc.row(r) ^ power.row(r)).collect(),
+                        4,
+                    );
+                }
+            }
+            acc
+        };
+        assert_eq!(evaluate(&chi), PackedGF2Matrix::new(vec![0; 4], 4));
+        let minimal = m.minimal_polynomial();
+        assert_eq!(evaluate(&minimal), PackedGF2Matrix::new(vec![0; 4], 4));
+        assert!(chi.rem(&minimal).is_zero());
+
+        // The identity has characteristic polynomial (x + 1)^n and minimal
+        // polynomial x + 1.
+        let identity = PackedGF2Matrix::<u16>::identity(5);
+        let x_plus_one = Gf2Poly::from_coefficients(&[1, 1]);
+        assert_eq!(
+            identity.characteristic_polynomial(),
+            x_plus_one.pow_mod(5, &Gf2Poly::monomial(6))
+        );
+        assert_eq!(identity.minimal_polynomial(), x_plus_one);
+    }
+
+    #[test]
+    fn test_companion_characteristic_polynomial_and_order() {
+        // 1 + x + x^3 + x^4 + x^64 is primitive, so the companion matrix has the
+        // reciprocal polynomial as characteristic polynomial and order 2^64 - 1.
+        let mut connection = Gf2Poly::from_coefficients(&[1, 1, 0, 1, 1]);
+        connection.toggle_coefficient(64);
+        let m = crate::lfsr::Lfsr::new(connection, 64).companion_matrix::<u64>();
+
+        let mut reciprocal = Gf2Poly::from_coefficients(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        for i in [60, 61, 63, 64] {
+            reciprocal.toggle_coefficient(i);
+        }
+        assert_eq!(m.characteristic_polynomial(), reciprocal);
+        assert_eq!(m.minimal_polynomial(), reciprocal);
+        assert_eq!(m.order(), Some(u64::MAX as u128));
+    }
+
+    #[test]
+    fn test_order_of_singular_and_block_matrices() {
+        let singular = PackedGF2Matrix::<u8>::new(vec![0b11, 0b11], 2);
+        assert_eq!(singular.order(), None);
+
+        // Block diagonal of a 3-cycle and a 2-cycle has order 6.
+        let m = PackedGF2Matrix::<u8>::new(vec![0b01000, 0b00100, 0b10000, 0b00001, 0b00010], 5);
+        assert_eq!(m.order(), Some(6));
+        assert_eq!(m.pow(6), PackedGF2Matrix::identity(5));
+    }
 }
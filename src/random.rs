@@ -0,0 +1,303 @@
+//! Random matrices over GF(2), available with the `rand` feature.
+//!
+//! Every generator takes any [`RngCore`], so that results are reproducible
+//! with a seeded generator.
+
+use alloc::{vec, vec::Vec};
+use rand_core::RngCore;
+
+use crate::matrix::Number;
+use crate::packed_gf2_matrix::PackedGF2Matrix;
+use crate::GF2Matrix;
+
+/// Returns a uniformly random bit.
+fn random_bit<R: RngCore + ?Sized>(rng: &mut R) -> u8 {
+    (rng.next_u32() >> 31) as u8
+}
+
+/// Returns a uniformly random vector of `n` bits.
+fn random_vector<R: RngCore + ?Sized>(n: usize, rng: &mut R) -> Vec<u8> {
+    (0..n).map(|_| random_bit(rng)).collect()
+}
+
+/// Returns `k` linearly independent rows of length `n`, uniformly among all
+/// such sequences.
+///
+/// With a basis `u_1, ..., u_i` of the span of the previous rows, where each
+/// `u_j` has a pivot `p_j` and vanishes on the earlier pivots, every vector is
+/// uniquely `sum(c_j * u_j) + w` with `w` vanishing on all pivots. The vectors
+/// outside the span are exactly those with `w != 0`, so drawing the `c_j`
+/// uniformly and `w` uniformly among the nonzero vectors is uniform, and no
+/// rank computation is ever rejected.
+fn independent_rows<R: RngCore + ?Sized>(k: usize, n: usize, rng: &mut R) -> Vec<Vec<u8>> {
+    assert!(
+        k <= n,
+        "cannot draw {} independent vectors of length {}",
+        k,
+        n
+    );
+    let mut basis: Vec<Vec<u8>> = Vec::with_capacity(k);
+    let mut is_pivot = vec![false; n];
+    let mut rows = Vec::with_capacity(k);
+    for _ in 0..k {
+        let free: Vec<usize> = (0..n).filter(|&j| !is_pivot[j]).collect();
+        let mut w = vec![0u8; n];
+        while w.iter().all(|&x| x == 0) {
+            for &j in &free {
+                w[j] = random_bit(rng);
+            }
+        }
+        let mut row = w.clone();
+        for u in &basis {
+            if random_bit(rng) == 1 {
+                for (x, &y) in row.iter_mut().zip(u) {
+                    *x ^= y;
+                }
+            }
+        }
+        let pivot = w.iter().position(|&x| x == 1).unwrap();
+        is_pivot[pivot] = true;
+        basis.push(w);
+        rows.push(row);
+    }
+    rows
+}
+
+/// Returns the transpose of `rows`, a matrix with `n` columns.
+fn transpose(rows: &[Vec<u8>], n: usize) -> Vec<Vec<u8>> {
+    (0..n)
+        .map(|j| rows.iter().map(|row| row[j]).collect())
+        .collect()
+}
+
+impl GF2Matrix {
+    /// Returns a uniformly random `m x n` matrix.
+    pub fn random<R: RngCore + ?Sized>(m: usize, n: usize, rng: &mut R) -> Self {
+        GF2Matrix::new((0..m).map(|_| random_vector(n, rng)).collect())
+    }
+
+    /// Returns a uniformly random `m x n` matrix of rank `min(m, n)`.
+    pub fn random_full_rank<R: RngCore + ?Sized>(m: usize, n: usize, rng: &mut R) -> Self {
+        if m <= n {
+            GF2Matrix::new(independent_rows(m, n, rng))
+        } else {
+            GF2Matrix::new(transpose(&independent_rows(n, m, rng), m))
+        }
+    }
+
+    /// Returns a uniformly random `m x n` matrix of rank `r`.
+    ///
+    /// The matrix is the product of uniformly random full rank `m x r` and
+    /// `r x n` matrices; every rank `r` matrix has the same number of such
+    /// factorisations, one per element of GL(r, 2).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r > min(m, n)`.
+    pub fn random_with_rank<R: RngCore + ?Sized>(
+        m: usize,
+        n: usize,
+        r: usize,
+        rng: &mut R,
+    ) -> Self {
+        assert!(
+            r <= m.min(n),
+            "rank {} is impossible for a {}x{} matrix",
+            r,
+            m,
+            n
+        );
+        let left = transpose(&independent_rows(r, m, rng), m);
+        let right = independent_rows(r, n, rng);
+        let elements = left
+            .iter()
+            .map(|l| {
+                let mut row = vec![0u8; n];
+                for (&c, u) in l.iter().zip(&right) {
+                    if c == 1 {
+                        for (x, &y) in row.iter_mut().zip(u) {
+                            *x ^= y;
+                        }
+                    }
+                }
+                row
+            })
+            .collect();
+        GF2Matrix::new(elements)
+    }
+
+    /// Returns a uniformly random element of GL(n, 2), the invertible `n x n`
+    /// matrices.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::gf2_matrix::GF2Matrix;
+    /// # use lin_algebra::matrix::MatrixTrait;
+    /// use rand_chacha::rand_core::SeedableRng;
+    ///
+    /// let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(7);
+    /// let m = GF2Matrix::random_invertible(16, &mut rng);
+    /// assert_eq!(m.rank(), 16);
+    /// ```
+    pub fn random_invertible<R: RngCore + ?Sized>(n: usize, rng: &mut R) -> Self {
+        Self::random_full_rank(n, n, rng)
+    }
+
+    /// Returns a random `m x n` matrix whose entries are `1` independently with
+    /// probability `density`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `density` is not in `[0, 1]`.
+    pub fn random_sparse<R: RngCore + ?Sized>(
+        m: usize,
+        n: usize,
+        density: f64,
+        rng: &mut R,
+    ) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&density),
+            "density {} is not a probability",
+            density
+        );
+        let elements = (0..m)
+            .map(|_| {
+                (0..n)
+                    .map(|_| {
+                        let sample = (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+                        (sample < density) as u8
+                    })
+                    .collect()
+            })
+            .collect();
+        GF2Matrix::new(elements)
+    }
+
+    /// Returns a uniformly random `n x n` upper triangular matrix, diagonal
+    /// included.
+    pub fn random_upper_triangular<R: RngCore + ?Sized>(n: usize, rng: &mut R) -> Self {
+        let elements = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| if j >= i { random_bit(rng) } else { 0 })
+                    .collect()
+            })
+            .collect();
+        GF2Matrix::new(elements)
+    }
+
+    /// Returns a uniformly random `n x n` lower triangular matrix, diagonal
+    /// included.
+    pub fn random_lower_triangular<R: RngCore + ?Sized>(n: usize, rng: &mut R) -> Self {
+        let upper = Self::random_upper_triangular(n, rng);
+        GF2Matrix::new(transpose(&upper.elements, n))
+    }
+}
+
+impl<T: Number> PackedGF2Matrix<T> {
+    /// Returns a uniformly random `m x n` matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` columns do not fit in `T`, as do the other generators.
+    pub fn random<R: RngCore + ?Sized>(m: usize, n: usize, rng: &mut R) -> Self {
+        GF2Matrix::random(m, n, rng).view().to_packed()
+    }
+
+    /// Returns a uniformly random `m x n` matrix of rank `min(m, n)`.
+    pub fn random_full_rank<R: RngCore + ?Sized>(m: usize, n: usize, rng: &mut R) -> Self {
+        GF2Matrix::random_full_rank(m, n, rng).view().to_packed()
+    }
+
+    /// Returns a uniformly random `m x n` matrix of rank `r`.
+    pub fn random_with_rank<R: RngCore + ?Sized>(
+        m: usize,
+        n: usize,
+        r: usize,
+        rng: &mut R,
+    ) -> Self {
+        GF2Matrix::random_with_rank(m, n, r, rng).view().to_packed()
+    }
+
+    /// Returns a uniformly random element of GL(n, 2).
+    pub fn random_invertible<R: RngCore + ?Sized>(n: usize, rng: &mut R) -> Self {
+        GF2Matrix::random_invertible(n, rng).view().to_packed()
+    }
+
+    /// Returns a random `m x n` matrix whose entries are `1` independently with
+    /// probability `density`.
+    pub fn random_sparse<R: RngCore + ?Sized>(
+        m: usize,
+        n: usize,
+        density: f64,
+        rng: &mut R,
+    ) -> Self {
+        GF2Matrix::random_sparse(m, n, density, rng)
+            .view()
+            .to_packed()
+    }
+
+    /// Returns a uniformly random `n x n` upper triangular matrix.
+    pub fn random_upper_triangular<R: RngCore + ?Sized>(n: usize, rng: &mut R) -> Self {
+        GF2Matrix::random_upper_triangular(n, rng)
+            .view()
+            .to_packed()
+    }
+
+    /// Returns a uniformly random `n x n` lower triangular matrix.
+    pub fn random_lower_triangular<R: RngCore + ?Sized>(n: usize, rng: &mut R) -> Self {
+        GF2Matrix::random_lower_triangular(n, rng)
+            .view()
+            .to_packed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_chacha::rand_core::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::matrix::MatrixTrait;
+
+    #[test]
+    fn test_ranks_and_shapes() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        for (m, n) in [(5, 9), (9, 5), (12, 12)] {
+            assert_eq!(GF2Matrix::random_full_rank(m, n, &mut rng).rank(), m.min(n));
+            for r in 0..=m.min(n) {
+                let a = GF2Matrix::random_with_rank(m, n, r, &mut rng);
+                assert_eq!((a.elements.len(), a.elements[0].len()), (m, n));
+                assert_eq!(a.rank(), r);
+            }
+        }
+        let p = PackedGF2Matrix::<u32>::random_invertible(32, &mut rng);
+        assert_eq!(p.rank(), 32);
+
+        let upper = GF2Matrix::random_upper_triangular(8, &mut rng);
+        let lower = PackedGF2Matrix::<u8>::random_lower_triangular(8, &mut rng);
+        for i in 0..8 {
+            assert!(upper.elements[i][..i].iter().all(|&x| x == 0));
+            assert_eq!(lower.row(i) & ((1u8 << (7 - i)).wrapping_sub(1)), 0);
+        }
+
+        let sparse = GF2Matrix::random_sparse(100, 100, 0.05, &mut rng);
+        let ones: usize = sparse.elements.iter().flatten().map(|&x| x as usize).sum();
+        assert!((300..700).contains(&ones));
+    }
+
+    #[test]
+    fn test_gl_sampler_is_uniform() {
+        // |GL(2, 2)| = 6: every invertible 2x2 matrix should appear about
+        // equally often.
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        let mut counts = std::collections::BTreeMap::new();
+        for _ in 0..6000 {
+            let m = PackedGF2Matrix::<u8>::random_invertible(2, &mut rng);
+            *counts.entry((m.row(0), m.row(1))).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 6);
+        assert!(counts.values().all(|&c| (850..1150).contains(&c)));
+    }
+}
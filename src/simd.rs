@@ -0,0 +1,463 @@
+//! Vectorised kernels on multi-word rows: XOR, dot-product parity and
+//! popcount.
+//!
+//! Each kernel dispatches at run time to AVX-512, AVX2 (x86-64) or NEON
+//! (AArch64) code when the CPU supports it, and falls back to scalar code
+//! otherwise. All code paths give identical results.
+
+#[cfg(any(feature = "std", test))]
+use alloc::{vec, vec::Vec};
+
+/// Instruction set used by the kernels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Isa {
+    Scalar,
+    #[cfg(target_arch = "x86_64")]
+    Avx2,
+    #[cfg(target_arch = "x86_64")]
+    Avx512,
+    #[cfg(target_arch = "aarch64")]
+    Neon,
+}
+
+/// Returns `true` if the CPU supports all the given target features. With
+/// `std` the CPU is queried at run time; without it, only the features enabled
+/// at compile time are used.
+#[cfg(any(feature = "std", test))]
+macro_rules! has_features {
+    ($detect:ident, $($feature:tt),+) => {{
+        #[cfg(feature = "std")]
+        let supported = $(std::arch::$detect!($feature))&&+;
+        #[cfg(not(feature = "std"))]
+        let supported = cfg!(all($(target_feature = $feature),+));
+        supported
+    }};
+}
+
+/// Returns the instruction sets supported by the running CPU, best last.
+#[cfg(any(feature = "std", test))]
+fn available() -> Vec<Isa> {
+    #[allow(unused_mut)]
+    let mut isas = vec![Isa::Scalar];
+    #[cfg(target_arch = "x86_64")]
+    {
+        if has_features!(is_x86_feature_detected, "avx2") {
+            isas.push(Isa::Avx2);
+        }
+        if has_features!(is_x86_feature_detected, "avx512f", "avx512bw") {
+            isas.push(Isa::Avx512);
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if has_features!(is_aarch64_feature_detected, "neon") {
+            isas.push(Isa::Neon);
+        }
+    }
+    isas
+}
+
+/// Returns the best instruction set of the running CPU, detected once.
+#[cfg(feature = "std")]
+fn detected() -> Isa {
+    static ISA: std::sync::OnceLock<Isa> = std::sync::OnceLock::new();
+    *ISA.get_or_init(|| *available().last().unwrap())
+}
+
+/// Returns the best instruction set enabled at compile time. This is a
+/// constant, so the kernels do no detection work on every call.
+#[cfg(not(feature = "std"))]
+const fn detected() -> Isa {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if cfg!(all(target_feature = "avx512f", target_feature = "avx512bw")) {
+            return Isa::Avx512;
+        }
+        if cfg!(target_feature = "avx2") {
+            return Isa::Avx2;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if cfg!(target_feature = "neon") {
+            return Isa::Neon;
+        }
+    }
+    Isa::Scalar
+}
+
+/// Adds `source` to `target` word by word, `target[i] ^= source[i]`.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::simd::xor_into;
+/// let mut row = vec![0b1100u64, u64::MAX];
+/// xor_into(&mut row, &[0b1010, 1]);
+/// assert_eq!(row, vec![0b0110, u64::MAX - 1]);
+/// ```
+pub fn xor_into(target: &mut [u64], source: &[u64]) {
+    assert_eq!(target.len(), source.len(), "rows must have the same length");
+    // SAFETY: both pointers are valid for `8 * len` bytes and `u8` has no
+    // alignment requirement.
+    unsafe {
+        xor_raw(
+            detected(),
+            target.as_mut_ptr().cast(),
+            source.as_ptr().cast(),
+            target.len() * 8,
+        )
+    }
+}
+
+/// Adds `source` to `target` byte by byte, `target[i] ^= source[i]`.
+///
+/// This is the row addition of [`GF2Matrix`](crate::GF2Matrix), whose entries
+/// are bytes equal to `0` or `1`.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+pub fn xor_bytes_into(target: &mut [u8], source: &[u8]) {
+    assert_eq!(target.len(), source.len(), "rows must have the same length");
+    // SAFETY: both pointers are valid for `len` bytes.
+    unsafe {
+        xor_raw(
+            detected(),
+            target.as_mut_ptr(),
+            source.as_ptr(),
+            target.len(),
+        )
+    }
+}
+
+/// Returns the parity of the dot product of two bit rows, that is the number
+/// of positions where both have a one, modulo 2.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::simd::dot_parity;
+/// assert_eq!(dot_parity(&[0b1110, 1], &[0b0110, 1]), 1);
+/// ```
+pub fn dot_parity(a: &[u64], b: &[u64]) -> u8 {
+    assert_eq!(a.len(), b.len(), "rows must have the same length");
+    dot_parity_with(detected(), a, b)
+}
+
+/// Returns the number of ones in a bit row.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::simd::popcount;
+/// assert_eq!(popcount(&[u64::MAX, 0b101]), 66);
+/// ```
+pub fn popcount(words: &[u64]) -> u64 {
+    popcount_with(detected(), words)
+}
+
+/// XORs `len` bytes from `source` into `target` with the given instruction set.
+///
+/// # Safety
+///
+/// `target` and `source` must be valid for `len` bytes, and `isa` must be
+/// supported by the running CPU.
+unsafe fn xor_raw(isa: Isa, target: *mut u8, source: *const u8, len: usize) {
+    match isa {
+        Isa::Scalar => scalar::xor(target, source, len),
+        #[cfg(target_arch = "x86_64")]
+        Isa::Avx2 => x86::xor_avx2(target, source, len),
+        #[cfg(target_arch = "x86_64")]
+        Isa::Avx512 => x86::xor_avx512(target, source, len),
+        #[cfg(target_arch = "aarch64")]
+        Isa::Neon => neon::xor(target, source, len),
+    }
+}
+
+fn dot_parity_with(isa: Isa, a: &[u64], b: &[u64]) -> u8 {
+    // SAFETY: `isa` comes from `available`, so the CPU supports it.
+    let folded = unsafe {
+        match isa {
+            Isa::Scalar => scalar::and_fold(a, b),
+            #[cfg(target_arch = "x86_64")]
+            Isa::Avx2 => x86::and_fold_avx2(a, b),
+            #[cfg(target_arch = "x86_64")]
+            Isa::Avx512 => x86::and_fold_avx512(a, b),
+            #[cfg(target_arch = "aarch64")]
+            Isa::Neon => neon::and_fold(a, b),
+        }
+    };
+    (folded.count_ones() & 1) as u8
+}
+
+fn popcount_with(isa: Isa, words: &[u64]) -> u64 {
+    // SAFETY: `isa` comes from `available`, so the CPU supports it.
+    unsafe {
+        match isa {
+            Isa::Scalar => scalar::popcount(words),
+            #[cfg(target_arch = "x86_64")]
+            Isa::Avx2 => x86::popcount_avx2(words),
+            #[cfg(target_arch = "x86_64")]
+            Isa::Avx512 => x86::popcount_avx512(words),
+            #[cfg(target_arch = "aarch64")]
+            Isa::Neon => neon::popcount(words),
+        }
+    }
+}
+
+/// Reference implementations, also used for the tails of vector loops.
+mod scalar {
+    /// # Safety
+    ///
+    /// `target` and `source` must be valid for `len` bytes.
+    pub(super) unsafe fn xor(target: *mut u8, source: *const u8, len: usize) {
+        for i in 0..len {
+            *target.add(i) ^= *source.add(i);
+        }
+    }
+
+    /// Returns the XOR of the words `a[i] & b[i]`, whose parity is the parity
+    /// of the dot product.
+    pub(super) fn and_fold(a: &[u64], b: &[u64]) -> u64 {
+        a.iter().zip(b).fold(0, |acc, (x, y)| acc ^ (x & y))
+    }
+
+    pub(super) fn popcount(words: &[u64]) -> u64 {
+        words.iter().map(|w| w.count_ones() as u64).sum()
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use core::arch::x86_64::*;
+
+    use super::scalar;
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn xor_avx2(target: *mut u8, source: *const u8, len: usize) {
+        let mut i = 0;
+        while i + 32 <= len {
+            let t = _mm256_loadu_si256(target.add(i).cast());
+            let s = _mm256_loadu_si256(source.add(i).cast());
+            _mm256_storeu_si256(target.add(i).cast(), _mm256_xor_si256(t, s));
+            i += 32;
+        }
+        scalar::xor(target.add(i), source.add(i), len - i);
+    }
+
+    #[target_feature(enable = "avx512f")]
+    pub(super) unsafe fn xor_avx512(target: *mut u8, source: *const u8, len: usize) {
+        let mut i = 0;
+        while i + 64 <= len {
+            let t = _mm512_loadu_si512(target.add(i).cast());
+            let s = _mm512_loadu_si512(source.add(i).cast());
+            _mm512_storeu_si512(target.add(i).cast(), _mm512_xor_si512(t, s));
+            i += 64;
+        }
+        xor_avx2(target.add(i), source.add(i), len - i);
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn and_fold_avx2(a: &[u64], b: &[u64]) -> u64 {
+        let mut acc = _mm256_setzero_si256();
+        let chunks = a.len() / 4;
+        for k in 0..chunks {
+            let x = _mm256_loadu_si256(a.as_ptr().add(4 * k).cast());
+            let y = _mm256_loadu_si256(b.as_ptr().add(4 * k).cast());
+            acc = _mm256_xor_si256(acc, _mm256_and_si256(x, y));
+        }
+        let mut lanes = [0u64; 4];
+        _mm256_storeu_si256(lanes.as_mut_ptr().cast(), acc);
+        lanes.iter().fold(0, |f, &l| f ^ l) ^ scalar::and_fold(&a[4 * chunks..], &b[4 * chunks..])
+    }
+
+    #[target_feature(enable = "avx512f")]
+    pub(super) unsafe fn and_fold_avx512(a: &[u64], b: &[u64]) -> u64 {
+        let mut acc = _mm512_setzero_si512();
+        let chunks = a.len() / 8;
+        for k in 0..chunks {
+            let x = _mm512_loadu_si512(a.as_ptr().add(8 * k).cast());
+            let y = _mm512_loadu_si512(b.as_ptr().add(8 * k).cast());
+            acc = _mm512_xor_si512(acc, _mm512_and_si512(x, y));
+        }
+        let mut lanes = [0u64; 8];
+        _mm512_storeu_si512(lanes.as_mut_ptr().cast(), acc);
+        lanes.iter().fold(0, |f, &l| f ^ l) ^ and_fold_avx2(&a[8 * chunks..], &b[8 * chunks..])
+    }
+
+    /// Counts the ones of 256 bits with nibble lookups (Muła's algorithm).
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn popcount_avx2(words: &[u64]) -> u64 {
+        let table = _mm256_setr_epi8(
+            0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4, 0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2,
+            3, 3, 4,
+        );
+        let low_nibbles = _mm256_set1_epi8(0x0f);
+        let mut acc = _mm256_setzero_si256();
+        let chunks = words.len() / 4;
+        for k in 0..chunks {
+            let v = _mm256_loadu_si256(words.as_ptr().add(4 * k).cast());
+            let lo = _mm256_and_si256(v, low_nibbles);
+            let hi = _mm256_and_si256(_mm256_srli_epi16(v, 4), low_nibbles);
+            let counts = _mm256_add_epi8(
+                _mm256_shuffle_epi8(table, lo),
+                _mm256_shuffle_epi8(table, hi),
+            );
+            acc = _mm256_add_epi64(acc, _mm256_sad_epu8(counts, _mm256_setzero_si256()));
+        }
+        let mut lanes = [0u64; 4];
+        _mm256_storeu_si256(lanes.as_mut_ptr().cast(), acc);
+        lanes.iter().sum::<u64>() + scalar::popcount(&words[4 * chunks..])
+    }
+
+    /// Counts the ones of 512 bits with nibble lookups (Muła's algorithm).
+    #[target_feature(enable = "avx512f,avx512bw")]
+    pub(super) unsafe fn popcount_avx512(words: &[u64]) -> u64 {
+        let table = _mm512_set4_epi32(0x0403_0302, 0x0302_0201, 0x0302_0201, 0x0201_0100);
+        let low_nibbles = _mm512_set1_epi8(0x0f);
+        let mut acc = _mm512_setzero_si512();
+        let chunks = words.len() / 8;
+        for k in 0..chunks {
+            let v = _mm512_loadu_si512(words.as_ptr().add(8 * k).cast());
+            let lo = _mm512_and_si512(v, low_nibbles);
+            let hi = _mm512_and_si512(_mm512_srli_epi16(v, 4), low_nibbles);
+            let counts = _mm512_add_epi8(
+                _mm512_shuffle_epi8(table, lo),
+                _mm512_shuffle_epi8(table, hi),
+            );
+            acc = _mm512_add_epi64(acc, _mm512_sad_epu8(counts, _mm512_setzero_si512()));
+        }
+        _mm512_reduce_add_epi64(acc) as u64 + popcount_avx2(&words[8 * chunks..])
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use core::arch::aarch64::*;
+
+    use super::scalar;
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn xor(target: *mut u8, source: *const u8, len: usize) {
+        let mut i = 0;
+        while i + 16 <= len {
+            let t = vld1q_u8(target.add(i));
+            let s = vld1q_u8(source.add(i));
+            vst1q_u8(target.add(i), veorq_u8(t, s));
+            i += 16;
+        }
+        scalar::xor(target.add(i), source.add(i), len - i);
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn and_fold(a: &[u64], b: &[u64]) -> u64 {
+        let mut acc = vdupq_n_u64(0);
+        let chunks = a.len() / 2;
+        for k in 0..chunks {
+            let x = vld1q_u64(a.as_ptr().add(2 * k));
+            let y = vld1q_u64(b.as_ptr().add(2 * k));
+            acc = veorq_u64(acc, vandq_u64(x, y));
+        }
+        vgetq_lane_u64(acc, 0)
+            ^ vgetq_lane_u64(acc, 1)
+            ^ scalar::and_fold(&a[2 * chunks..], &b[2 * chunks..])
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn popcount(words: &[u64]) -> u64 {
+        let mut total = 0u64;
+        let chunks = words.len() / 2;
+        for k in 0..chunks {
+            let v = vld1q_u8(words.as_ptr().add(2 * k).cast());
+            total += vaddlvq_u8(vcntq_u8(v)) as u64;
+        }
+        total + scalar::popcount(&words[2 * chunks..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_rng::xorshift64;
+
+    fn random_words(n: usize, state: &mut u64) -> Vec<u64> {
+        (0..n).map(|_| xorshift64(state)).collect()
+    }
+
+    #[test]
+    fn test_kernels_match_scalar_reference() {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        for len in (0..40).chain([63, 64, 65, 127, 128, 129, 1000]) {
+            let a = random_words(len + 1, &mut state);
+            let b = random_words(len + 1, &mut state);
+            // Offset slices are not aligned to the vector width.
+            for (a, b) in [(&a[..len], &b[..len]), (&a[1..], &b[1..])] {
+                let bytes: Vec<u8> = a.iter().map(|&w| (w & 1) as u8).collect();
+                let other_bytes: Vec<u8> = b.iter().map(|&w| (w & 1) as u8).collect();
+                for isa in available() {
+                    let mut expected = a.to_vec();
+                    let mut xored = a.to_vec();
+                    let mut expected_bytes = bytes.clone();
+                    let mut xored_bytes = bytes.clone();
+                    unsafe {
+                        scalar::xor(expected.as_mut_ptr().cast(), b.as_ptr().cast(), 8 * a.len());
+                        xor_raw(
+                            isa,
+                            xored.as_mut_ptr().cast(),
+                            b.as_ptr().cast(),
+                            8 * a.len(),
+                        );
+                        scalar::xor(
+                            expected_bytes.as_mut_ptr(),
+                            other_bytes.as_ptr(),
+                            bytes.len(),
+                        );
+                        xor_raw(
+                            isa,
+                            xored_bytes.as_mut_ptr(),
+                            other_bytes.as_ptr(),
+                            bytes.len(),
+                        );
+                    }
+                    assert_eq!(xored, expected, "{:?} xor, {} words", isa, a.len());
+                    assert_eq!(xored_bytes, expected_bytes, "{:?} byte xor", isa);
+                    assert_eq!(
+                        dot_parity_with(isa, a, b),
+                        dot_parity_with(Isa::Scalar, a, b),
+                        "{:?} dot parity, {} words",
+                        isa,
+                        a.len()
+                    );
+                    assert_eq!(
+                        popcount_with(isa, a),
+                        popcount_with(Isa::Scalar, a),
+                        "{:?} popcount, {} words",
+                        isa,
+                        a.len()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_scalar_reference() {
+        let a = [0b1011u64, u64::MAX, 0];
+        let b = [0b0110u64, 1 << 63, u64::MAX];
+        let mut c = a;
+        xor_into(&mut c, &b);
+        assert_eq!(c, [0b1101, u64::MAX >> 1, u64::MAX]);
+        assert_eq!(dot_parity(&a, &b), 0);
+        assert_eq!(popcount(&a), 67);
+        assert_eq!(popcount(&[]), 0);
+    }
+}
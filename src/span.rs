@@ -0,0 +1,222 @@
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use crate::matrix::Number;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Iterator over the linear span of a packed basis, or a coset of it, in
+/// Gray-code order.
+///
+/// Element `i` is the offset plus the basis vectors selected by the bits of the
+/// Gray code `i ^ (i >> 1)`. Consecutive Gray codes differ in a single bit, so
+/// each step is one XOR of a basis vector, and the iterator does not allocate
+/// when the basis is borrowed. The basis vectors must be linearly independent
+/// for the `2^k` elements to be distinct.
+///
+/// The iterator is lazy, so adaptors such as [`Iterator::find`] stop the
+/// enumeration at the first match. [`SpanIter::chunks`] splits the
+/// enumeration in contiguous ranges of Gray indices that can be run on
+/// different threads, and with the `rayon` feature `SpanIter::par_chunks`
+/// returns them as a parallel iterator.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::packed_gf2_matrix::PackedGF2Matrix;
+/// # use lin_algebra::span::SpanIter;
+/// let m = PackedGF2Matrix::new(vec![0b1110u8, 0b0111], 4);
+/// let kernel = m.kernel();
+///
+/// // The minimum weight of a nonzero vector of the kernel.
+/// let weight = SpanIter::new(&kernel[..])
+///     .filter(|&v| v != 0)
+///     .map(|v| v.count_ones())
+///     .min();
+/// assert_eq!(weight, Some(2));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpanIter<T: Number, B: AsRef<[T]>> {
+    basis: B,
+    current: T,
+    index: usize,
+    end: usize,
+    word: PhantomData<T>,
+}
+
+impl<T: Number, B: AsRef<[T]>> SpanIter<T, B> {
+    /// Iterates over the span of `basis`, starting with zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `2^k` elements spanned by `k` basis vectors do not fit in
+    /// a `usize`.
+    pub fn new(basis: B) -> Self {
+        Self::with_offset(basis, T::zero())
+    }
+
+    /// Iterates over the coset `offset + span(basis)`, starting with `offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics as [`SpanIter::new`].
+    pub fn with_offset(basis: B, offset: T) -> Self {
+        let len = Self::span_len(&basis);
+        Self::range(basis, offset, 0..len)
+    }
+
+    /// Iterates over the elements of `offset + span(basis)` whose Gray indices
+    /// lie in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics as [`SpanIter::new`], or if `range` is not within `0..2^k`.
+    pub fn range(basis: B, offset: T, range: Range<usize>) -> Self {
+        let len = Self::span_len(&basis);
+        assert!(
+            range.start <= range.end && range.end <= len,
+            "range {}..{} out of bounds for a span of {} elements",
+            range.start,
+            range.end,
+            len
+        );
+        let gray = range.start ^ (range.start >> 1);
+        let current = basis
+            .as_ref()
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| (gray >> i) & 1 == 1)
+            .fold(offset, |acc, (_, &v)| acc ^ v);
+        Self {
+            basis,
+            current,
+            index: range.start,
+            end: range.end,
+            word: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements spanned by `basis`.
+    fn span_len(basis: &B) -> usize {
+        let k = basis.as_ref().len();
+        assert!(
+            k < usize::BITS as usize,
+            "a basis of {} vectors spans too many elements",
+            k
+        );
+        1 << k
+    }
+
+    /// Returns `count` iterators over contiguous ranges of Gray indices that
+    /// together enumerate `offset + span(basis)` once.
+    ///
+    /// # Panics
+    ///
+    /// Panics as [`SpanIter::new`], or if `count` is zero.
+    pub fn chunks(basis: B, offset: T, count: usize) -> impl Iterator<Item = Self>
+    where
+        B: Clone,
+    {
+        assert!(count > 0, "the span must be split in at least one chunk");
+        let len = Self::span_len(&basis);
+        (0..count).map(move |c| Self::range(basis.clone(), offset, chunk(len, count, c)))
+    }
+
+    /// Returns [`SpanIter::chunks`] as a parallel iterator.
+    ///
+    /// Early termination across chunks is available through the short-circuiting
+    /// rayon adaptors, e.g. `find_map_any(|mut chunk| chunk.find(..))`.
+    ///
+    /// # Panics
+    ///
+    /// Panics as [`SpanIter::chunks`].
+    #[cfg(feature = "rayon")]
+    pub fn par_chunks(
+        basis: B,
+        offset: T,
+        count: usize,
+    ) -> impl IndexedParallelIterator<Item = Self>
+    where
+        B: Clone + Send + Sync,
+    {
+        assert!(count > 0, "the span must be split in at least one chunk");
+        let len = Self::span_len(&basis);
+        (0..count)
+            .into_par_iter()
+            .map(move |c| Self::range(basis.clone(), offset, chunk(len, count, c)))
+    }
+}
+
+/// Returns the range of chunk `c` when `len` indices are split in `count`
+/// chunks whose sizes differ by at most one.
+fn chunk(len: usize, count: usize, c: usize) -> Range<usize> {
+    let bound = |c: usize| (len / count) * c + (len % count).min(c);
+    bound(c)..bound(c + 1)
+}
+
+impl<T: Number, B: AsRef<[T]>> Iterator for SpanIter<T, B> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.index == self.end {
+            return None;
+        }
+        let element = self.current;
+        self.index += 1;
+        if self.index < self.end {
+            // The Gray codes of `index - 1` and `index` differ in the bit of
+            // the lowest one of `index`.
+            self.current = self.current ^ self.basis.as_ref()[self.index.trailing_zeros() as usize];
+        }
+        Some(element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T: Number, B: AsRef<[T]>> ExactSizeIterator for SpanIter<T, B> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_span_in_gray_code_order() {
+        let basis = [0b0001u8, 0b0110, 0b1000];
+        let elements: Vec<u8> = SpanIter::with_offset(&basis[..], 0b0100).collect();
+        assert_eq!(
+            elements,
+            vec![0b0100, 0b0101, 0b0011, 0b0010, 0b1010, 0b1011, 0b1101, 0b1100]
+        );
+
+        for count in [1, 3, 8, 11] {
+            let chunked: Vec<u8> = SpanIter::chunks(&basis[..], 0b0100, count)
+                .flatten()
+                .collect();
+            assert_eq!(chunked, elements);
+        }
+        assert_eq!(SpanIter::<u8, _>::new(&[][..]).collect::<Vec<_>>(), vec![0]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_search() {
+        let basis: Vec<u32> = (0..20)
+            .map(|i| 0x9e37_79b9u32.rotate_left(i) ^ (1 << i))
+            .collect();
+        let target = basis[3] ^ basis[11] ^ basis[19];
+
+        let found = SpanIter::par_chunks(&basis[..], 0, 16)
+            .find_map_any(|mut chunk| chunk.find(|&v| v == target));
+        assert_eq!(found, Some(target));
+        let count: usize = SpanIter::par_chunks(&basis[..], 0, 16)
+            .map(|c| c.len())
+            .sum();
+        assert_eq!(count, 1 << 20);
+    }
+}
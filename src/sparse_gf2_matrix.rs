@@ -0,0 +1,654 @@
+use alloc::collections::BinaryHeap;
+use alloc::{vec, vec::Vec};
+use core::cmp::Reverse;
+
+use crate::matrix::Number;
+use crate::packed_gf2_matrix::PackedGF2Matrix;
+use crate::simd;
+
+/// Default bound on the Markowitz cost `(row_weight - 1) * (column_weight - 1)`
+/// of the pivots taken during structured Gaussian elimination.
+///
+/// Once the cheapest available pivot is more expensive than this, the sparse
+/// phase stops and the remaining rows are eliminated densely.
+pub const DEFAULT_MARKOWITZ_THRESHOLD: usize = 64;
+
+/// A sparse matrix over GF(2) in compressed sparse row (CSR) form.
+///
+/// Only the column indices of the nonzero entries are stored: row `i` holds
+/// the sorted indices `col_indices[row_ptr[i]..row_ptr[i + 1]]`. The
+/// compressed sparse column (CSC) form of a matrix is the CSR form of its
+/// transpose, see [`Self::transpose`].
+///
+/// This type targets the huge, very sparse systems of index calculus and
+/// algebraic cryptanalysis, which fit neither in a `GF2Matrix` nor in a
+/// `PackedGF2Matrix`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGF2Matrix {
+    nrows: usize,
+    ncols: usize,
+    row_ptr: Vec<usize>,
+    col_indices: Vec<usize>,
+}
+
+/// The outcome of structured Gaussian elimination on a [`SparseGF2Matrix`].
+///
+/// The sparse phase records pivot rows in elimination order; the rows it could
+/// not eliminate cheaply form the dense remainder, which is reduced with the
+/// dense echelon form.
+#[derive(Clone, Debug)]
+pub struct StructuredElimination {
+    ncols: usize,
+    /// `(pivot column, row, right-hand side)` in elimination order. Every row
+    /// only contains its pivot and columns pivoted later or kept in the dense
+    /// remainder.
+    sparse_pivots: Vec<(usize, Vec<usize>, u8)>,
+    /// Reduced rows of the dense remainder, as `(pivot column, row, right-hand side)`.
+    dense_pivots: Vec<(usize, Vec<usize>, u8)>,
+    remainder_shape: (usize, usize),
+    consistent: bool,
+}
+
+impl SparseGF2Matrix {
+    /// Creates a sparse matrix from the column indices of the nonzero entries of
+    /// each row.
+    ///
+    /// Indices within a row may be given in any order. Since entries are added
+    /// over GF(2), an index repeated twice cancels out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is not smaller than `ncols`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::sparse_gf2_matrix::SparseGF2Matrix;
+    /// let m = SparseGF2Matrix::from_rows(3, vec![vec![0, 2], vec![1], vec![2, 0, 1]]);
+    ///
+    /// assert_eq!(m.row(2), &[0, 1, 2]);
+    /// assert_eq!(m.nnz(), 6);
+    /// ```
+    pub fn from_rows(ncols: usize, rows: Vec<Vec<usize>>) -> Self {
+        let mut row_ptr = Vec::with_capacity(rows.len() + 1);
+        let mut col_indices = Vec::new();
+        row_ptr.push(0);
+        for mut row in rows {
+            row.sort_unstable();
+            let mut i = 0;
+            while i < row.len() {
+                let col = row[i];
+                assert!(col < ncols, "column index {} out of bounds", col);
+                let mut count = 0;
+                while i < row.len() && row[i] == col {
+                    count += 1;
+                    i += 1;
+                }
+                if count % 2 == 1 {
+                    col_indices.push(col);
+                }
+            }
+            row_ptr.push(col_indices.len());
+        }
+        Self {
+            nrows: row_ptr.len() - 1,
+            ncols,
+            row_ptr,
+            col_indices,
+        }
+    }
+
+    /// Returns the number of rows.
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    /// Returns the number of columns.
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    /// Returns the number of nonzero entries.
+    pub fn nnz(&self) -> usize {
+        self.col_indices.len()
+    }
+
+    /// Returns the sorted column indices of the nonzero entries of row `i`.
+    pub fn row(&self, i: usize) -> &[usize] {
+        &self.col_indices[self.row_ptr[i]..self.row_ptr[i + 1]]
+    }
+
+    /// Returns the transpose of the matrix.
+    ///
+    /// The rows of the transpose are the columns of `self`, so this is also the
+    /// compressed sparse column form of `self`.
+    pub fn transpose(&self) -> SparseGF2Matrix {
+        let mut counts = vec![0; self.ncols + 1];
+        for &col in &self.col_indices {
+            counts[col + 1] += 1;
+        }
+        for j in 0..self.ncols {
+            counts[j + 1] += counts[j];
+        }
+        let row_ptr = counts.clone();
+        let mut next = counts;
+        let mut col_indices = vec![0; self.nnz()];
+        for i in 0..self.nrows {
+            for &col in self.row(i) {
+                col_indices[next[col]] = i;
+                next[col] += 1;
+            }
+        }
+        SparseGF2Matrix {
+            nrows: self.ncols,
+            ncols: self.nrows,
+            row_ptr,
+            col_indices,
+        }
+    }
+
+    /// Multiplies the matrix by the column vector `v` of length `ncols`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v.len() != self.ncols()`.
+    pub fn mul_vector(&self, v: &[u8]) -> Vec<u8> {
+        assert_eq!(
+            v.len(),
+            self.ncols,
+            "vector length must match the number of columns"
+        );
+        (0..self.nrows)
+            .map(|i| self.row(i).iter().fold(0, |acc, &j| acc ^ v[j]))
+            .collect()
+    }
+
+    /// Runs structured Gaussian elimination on the matrix.
+    ///
+    /// Pivots are chosen greedily with Markowitz pivoting: the column of least
+    /// weight is pivoted on its lightest row. Columns of weight one (singletons)
+    /// and two (doubletons) thus go first and are pruned without any fill-in.
+    /// The sparse phase stops once the Markowitz cost of the next pivot exceeds
+    /// `max_markowitz_cost`; the remaining rows and columns are then reduced in
+    /// dense packed form, with the echelon form of [`PackedGF2Matrix`] on `u64`
+    /// or `u128` rows up to 128 columns, and on rows of several `u64` words
+    /// added with [`simd::xor_into`] beyond.
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - optional right-hand side carried along the row operations.
+    /// * `max_markowitz_cost` - bound on the cost of sparse pivots.
+    pub fn structured_elimination(
+        &self,
+        rhs: Option<&[u8]>,
+        max_markowitz_cost: usize,
+    ) -> StructuredElimination {
+        if let Some(b) = rhs {
+            assert_eq!(
+                b.len(),
+                self.nrows,
+                "right-hand side length must match the number of rows"
+            );
+        }
+
+        let mut rows: Vec<Vec<usize>> = (0..self.nrows).map(|i| self.row(i).to_vec()).collect();
+        let mut rhs: Vec<u8> = rhs.map_or_else(|| vec![0; self.nrows], |b| b.to_vec());
+        let mut row_active = vec![true; self.nrows];
+        let mut col_active = vec![true; self.ncols];
+        let mut col_weight = vec![0usize; self.ncols];
+        // Rows that may contain each column; entries go stale after fill-in
+        // cancellations and are checked on use.
+        let mut col_rows: Vec<Vec<usize>> = vec![Vec::new(); self.ncols];
+        for (i, row) in rows.iter().enumerate() {
+            for &j in row {
+                col_weight[j] += 1;
+                col_rows[j].push(i);
+            }
+        }
+
+        let mut heap: BinaryHeap<Reverse<(usize, usize)>> = (0..self.ncols)
+            .filter(|&j| col_weight[j] > 0)
+            .map(|j| Reverse((col_weight[j], j)))
+            .collect();
+        let mut sparse_pivots = Vec::new();
+
+        while let Some(Reverse((weight, col))) = heap.pop() {
+            if !col_active[col] || col_weight[col] != weight || weight == 0 {
+                continue;
+            }
+
+            let mut candidates: Vec<usize> = col_rows[col]
+                .iter()
+                .copied()
+                .filter(|&i| row_active[i] && rows[i].binary_search(&col).is_ok())
+                .collect();
+            candidates.sort_unstable();
+            candidates.dedup();
+            col_rows[col].clone_from(&candidates);
+
+            let pivot_row = *candidates.iter().min_by_key(|&&i| rows[i].len()).unwrap();
+            let cost = (rows[pivot_row].len() - 1) * (weight - 1);
+            if cost > max_markowitz_cost {
+                break;
+            }
+
+            let pivot = core::mem::take(&mut rows[pivot_row]);
+            let mut changed = Vec::new();
+            for &i in candidates.iter().filter(|&&i| i != pivot_row) {
+                let (sum, added, removed) = symmetric_difference(&rows[i], &pivot);
+                rows[i] = sum;
+                rhs[i] ^= rhs[pivot_row];
+                for j in added {
+                    col_weight[j] += 1;
+                    col_rows[j].push(i);
+                    changed.push(j);
+                }
+                for j in removed {
+                    col_weight[j] -= 1;
+                    changed.push(j);
+                }
+            }
+            for &j in &pivot {
+                col_weight[j] -= 1;
+                changed.push(j);
+            }
+            row_active[pivot_row] = false;
+            col_active[col] = false;
+
+            changed.sort_unstable();
+            changed.dedup();
+            for j in changed {
+                if col_active[j] && col_weight[j] > 0 {
+                    heap.push(Reverse((col_weight[j], j)));
+                }
+            }
+            sparse_pivots.push((col, pivot, rhs[pivot_row]));
+        }
+
+        // Dense remainder: the active rows only contain active columns.
+        let mut consistent = true;
+        let mut remainder_rows = Vec::new();
+        for i in 0..self.nrows {
+            if !row_active[i] {
+                continue;
+            }
+            if rows[i].is_empty() {
+                consistent &= rhs[i] == 0;
+            } else {
+                remainder_rows.push(i);
+            }
+        }
+        let remainder_cols: Vec<usize> = (0..self.ncols)
+            .filter(|&j| col_active[j] && col_weight[j] > 0)
+            .collect();
+        let mut local_index = vec![usize::MAX; self.ncols];
+        for (k, &j) in remainder_cols.iter().enumerate() {
+            local_index[j] = k;
+        }
+
+        // The right-hand side is the last column of the dense system, so a pivot
+        // there means the system is inconsistent.
+        let width = remainder_cols.len() + 1;
+        let dense_rows: Vec<Vec<usize>> = remainder_rows
+            .iter()
+            .map(|&i| {
+                let mut row: Vec<usize> = rows[i].iter().map(|&j| local_index[j]).collect();
+                if rhs[i] == 1 {
+                    row.push(width - 1);
+                }
+                row
+            })
+            .collect();
+
+        let mut dense_pivots = Vec::new();
+        for row in dense_rref(&dense_rows, width) {
+            if row[0] == width - 1 {
+                consistent = false;
+                continue;
+            }
+            let b = (row.last() == Some(&(width - 1))) as u8;
+            let cols: Vec<usize> = row
+                .iter()
+                .filter(|&&k| k != width - 1)
+                .map(|&k| remainder_cols[k])
+                .collect();
+            dense_pivots.push((cols[0], cols, b));
+        }
+
+        StructuredElimination {
+            ncols: self.ncols,
+            sparse_pivots,
+            dense_pivots,
+            remainder_shape: (remainder_rows.len(), remainder_cols.len()),
+            consistent,
+        }
+    }
+
+    /// Computes the rank of the matrix with structured Gaussian elimination.
+    pub fn rank(&self) -> usize {
+        self.structured_elimination(None, DEFAULT_MARKOWITZ_THRESHOLD)
+            .rank()
+    }
+
+    /// Computes a basis of the kernel `{x : A x = 0}` of the matrix with
+    /// structured Gaussian elimination.
+    ///
+    /// # Returns
+    ///
+    /// The basis vectors, each of length `ncols`, with entries `0` or `1`.
+    pub fn kernel(&self) -> Vec<Vec<u8>> {
+        self.structured_elimination(None, DEFAULT_MARKOWITZ_THRESHOLD)
+            .kernel()
+    }
+
+    /// Solves `A x = b` with structured Gaussian elimination.
+    ///
+    /// Unlike the dense solvers, the matrix does not need full column rank: any
+    /// solution is returned, with all free variables set to zero.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the system is inconsistent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use lin_algebra::sparse_gf2_matrix::SparseGF2Matrix;
+    /// let m = SparseGF2Matrix::from_rows(4, vec![vec![0, 1], vec![1, 2], vec![2, 3]]);
+    /// let x = m.solve(&[1, 0, 1]).unwrap();
+    ///
+    /// assert_eq!(m.mul_vector(&x), vec![1, 0, 1]);
+    /// ```
+    pub fn solve(&self, b: &[u8]) -> Option<Vec<u8>> {
+        self.structured_elimination(Some(b), DEFAULT_MARKOWITZ_THRESHOLD)
+            .particular_solution()
+    }
+}
+
+impl StructuredElimination {
+    /// Returns the rank of the eliminated matrix.
+    pub fn rank(&self) -> usize {
+        self.sparse_pivots.len() + self.dense_pivots.len()
+    }
+
+    /// Returns the number of pivots taken by the sparse phase.
+    pub fn sparse_pivot_count(&self) -> usize {
+        self.sparse_pivots.len()
+    }
+
+    /// Returns the shape `(rows, columns)` of the dense remainder handed to the
+    /// dense echelon form.
+    pub fn remainder_shape(&self) -> (usize, usize) {
+        self.remainder_shape
+    }
+
+    /// Returns `false` if the right-hand side made the system inconsistent.
+    pub fn is_consistent(&self) -> bool {
+        self.consistent
+    }
+
+    /// Returns a basis of the kernel of the eliminated matrix.
+    pub fn kernel(&self) -> Vec<Vec<u8>> {
+        let mut is_pivot = vec![false; self.ncols];
+        for (col, _, _) in self.sparse_pivots.iter().chain(&self.dense_pivots) {
+            is_pivot[*col] = true;
+        }
+
+        (0..self.ncols)
+            .filter(|&j| !is_pivot[j])
+            .map(|free| {
+                let mut x = vec![0u8; self.ncols];
+                x[free] = 1;
+                self.back_substitute(&mut x, false);
+                x
+            })
+            .collect()
+    }
+
+    /// Returns a solution of the system with all free variables set to zero, or
+    /// `None` if it is inconsistent.
+    pub fn particular_solution(&self) -> Option<Vec<u8>> {
+        if !self.consistent {
+            return None;
+        }
+        let mut x = vec![0u8; self.ncols];
+        self.back_substitute(&mut x, true);
+        Some(x)
+    }
+
+    /// Fills in the pivot variables of `x` from its free variables, dense pivots
+    /// first and then sparse pivots in reverse elimination order.
+    fn back_substitute(&self, x: &mut [u8], with_rhs: bool) {
+        for (col, row, b) in self
+            .dense_pivots
+            .iter()
+            .chain(self.sparse_pivots.iter().rev())
+        {
+            let mut value = if with_rhs { *b } else { 0 };
+            for &j in row {
+                if j != *col {
+                    value ^= x[j];
+                }
+            }
+            x[*col] = value;
+        }
+    }
+}
+
+/// Returns the symmetric difference of two sorted index lists together with the
+/// indices only in `b` (added to `a`) and those in both (removed from `a`).
+fn symmetric_difference(a: &[usize], b: &[usize]) -> (Vec<usize>, Vec<usize>, Vec<usize>) {
+    let mut sum = Vec::with_capacity(a.len() + b.len());
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if j == b.len() || (i < a.len() && a[i] < b[j]) {
+            sum.push(a[i]);
+            i += 1;
+        } else if i == a.len() || b[j] < a[i] {
+            sum.push(b[j]);
+            added.push(b[j]);
+            j += 1;
+        } else {
+            removed.push(a[i]);
+            i += 1;
+            j += 1;
+        }
+    }
+    (sum, added, removed)
+}
+
+/// Computes the nonzero rows of the reduced row echelon form of a dense matrix
+/// given as sorted index lists, returned as sorted index lists.
+///
+/// The matrix is always reduced in bit-packed form: up to 64 and 128 columns
+/// in place with [`PackedGF2Matrix::echelon_form_in_place_with_ops`] on `u64`
+/// and `u128` rows, wider ones on rows of several `u64` words added with
+/// [`simd::xor_into`].
+pub(crate) fn dense_rref(rows: &[Vec<usize>], ncols: usize) -> Vec<Vec<usize>> {
+    if rows.is_empty() {
+        return Vec::new();
+    }
+    if ncols <= 64 {
+        packed_rref::<u64>(rows, ncols)
+    } else if ncols <= 128 {
+        packed_rref::<u128>(rows, ncols)
+    } else {
+        multiword_rref(rows, ncols)
+    }
+}
+
+/// [`dense_rref`] on a matrix whose rows fit in a single word of type `T`.
+fn packed_rref<T: Number>(rows: &[Vec<usize>], ncols: usize) -> Vec<Vec<usize>> {
+    let packed: Vec<T> = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .fold(T::zero(), |acc, &j| acc ^ (T::one() << (ncols - 1 - j)))
+        })
+        .collect();
+    let mut echelon = PackedGF2Matrix::new(packed, ncols);
+    echelon.echelon_form_in_place_with_ops(&mut Vec::new());
+    echelon
+        .image_echelon_form()
+        .into_iter()
+        .map(|row| {
+            (0..ncols)
+                .filter(|&j| (row >> (ncols - 1 - j)) & T::one() != T::zero())
+                .collect()
+        })
+        .collect()
+}
+
+/// [`dense_rref`] on rows of `ceil(ncols / 64)` words, column `j` being bit
+/// `63 - j % 64` of word `j / 64`.
+fn multiword_rref(rows: &[Vec<usize>], ncols: usize) -> Vec<Vec<usize>> {
+    let words = ncols.div_ceil(64);
+    let bit = |j: usize| (j / 64, 1u64 << (63 - j % 64));
+    let mut packed: Vec<Vec<u64>> = rows
+        .iter()
+        .map(|row| {
+            let mut words = vec![0u64; words];
+            for &j in row {
+                let (w, mask) = bit(j);
+                words[w] ^= mask;
+            }
+            words
+        })
+        .collect();
+
+    // Gauss-Jordan elimination, one pivot column at a time.
+    let mut rank = 0;
+    for col in 0..ncols {
+        if rank == packed.len() {
+            break;
+        }
+        let (w, mask) = bit(col);
+        let Some(pivot) = (rank..packed.len()).find(|&i| packed[i][w] & mask != 0) else {
+            continue;
+        };
+        packed.swap(rank, pivot);
+        let pivot_row = packed[rank].clone();
+        for (i, row) in packed.iter_mut().enumerate() {
+            if i != rank && row[w] & mask != 0 {
+                simd::xor_into(row, &pivot_row);
+            }
+        }
+        rank += 1;
+    }
+
+    packed
+        .iter()
+        .take(rank)
+        .map(|row| {
+            let mut indices = Vec::new();
+            for (w, &word) in row.iter().enumerate() {
+                let mut rest = word;
+                while rest != 0 {
+                    let offset = rest.leading_zeros() as usize;
+                    indices.push(w * 64 + offset);
+                    rest ^= 1 << (63 - offset);
+                }
+            }
+            indices
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::MatrixTrait;
+    use crate::test_rng::{random_sparse, xorshift64};
+    use crate::GF2Matrix;
+
+    #[test]
+    fn test_conversions_and_transpose() {
+        let m = SparseGF2Matrix::from_rows(4, vec![vec![3, 0], vec![], vec![1, 1, 2]]);
+        let dense = GF2Matrix::from(&m);
+        assert_eq!(
+            dense.elements,
+            vec![vec![1, 0, 0, 1], vec![0, 0, 0, 0], vec![0, 0, 1, 0]]
+        );
+        assert_eq!(SparseGF2Matrix::from(&dense), m);
+
+        let packed = PackedGF2Matrix::<u8>::from(&m);
+        assert_eq!(
+            packed,
+            PackedGF2Matrix::new(vec![0b1001, 0b0000, 0b0010], 4)
+        );
+        assert_eq!(SparseGF2Matrix::from(&packed), m);
+
+        let t = m.transpose();
+        assert_eq!(t.nrows(), 4);
+        assert_eq!(t.row(0), &[0]);
+        assert_eq!(t.row(2), &[2]);
+        assert_eq!(t.row(3), &[0]);
+        assert_eq!(t.transpose(), m);
+    }
+
+    #[test]
+    fn test_rank_and_kernel_match_dense() {
+        for seed in 1..20 {
+            let m = random_sparse(40, 50, 3, seed);
+            let dense = GF2Matrix::from(&m);
+            assert_eq!(m.rank(), dense.rank());
+
+            let kernel = m.kernel();
+            assert_eq!(kernel.len(), 50 - dense.rank());
+            for v in &kernel {
+                assert!(m.mul_vector(v).iter().all(|&b| b == 0));
+            }
+            // The basis vectors are independent.
+            assert_eq!(GF2Matrix::new(kernel).rank(), 50 - dense.rank());
+        }
+    }
+
+    #[test]
+    fn test_dense_remainder_paths() {
+        let m = random_sparse(150, 120, 6, 7);
+        let dense_rank = GF2Matrix::from(&m).rank();
+
+        // With no sparse pivots allowed beyond singletons, the remainder is wide
+        // and goes through a packed path of several words per row.
+        let elimination = m.structured_elimination(None, 0);
+        assert!(elimination.remainder_shape().1 > 64);
+        assert_eq!(elimination.rank(), dense_rank);
+
+        let elimination = m.structured_elimination(None, usize::MAX);
+        assert_eq!(elimination.remainder_shape(), (0, 0));
+        assert_eq!(elimination.rank(), dense_rank);
+
+        // Every packed path of `dense_rref` gives the reduced echelon form.
+        for ncols in [50, 100, 200] {
+            let m = random_sparse(ncols / 2, ncols, 8, ncols as u64);
+            let rows: Vec<Vec<usize>> = (0..m.nrows()).map(|i| m.row(i).to_vec()).collect();
+            let (echelon, _) = GF2Matrix::from(&m).echelon_form();
+            let expected: Vec<Vec<usize>> = echelon
+                .elements
+                .iter()
+                .map(|row| (0..ncols).filter(|&j| row[j] == 1).collect::<Vec<_>>())
+                .filter(|row| !row.is_empty())
+                .collect();
+            assert_eq!(dense_rref(&rows, ncols), expected);
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let m = random_sparse(60, 80, 4, 3);
+        let mut state = 99;
+        let x: Vec<u8> = (0..80)
+            .map(|_| (xorshift64(&mut state) & 1) as u8)
+            .collect();
+        let b = m.mul_vector(&x);
+        let solution = m.solve(&b).unwrap();
+        assert_eq!(m.mul_vector(&solution), b);
+
+        // x0 + x1 = 1, x1 = 0, x0 = 0 is inconsistent.
+        let m = SparseGF2Matrix::from_rows(2, vec![vec![0, 1], vec![1], vec![0]]);
+        assert_eq!(m.solve(&[1, 0, 0]), None);
+        assert_eq!(m.solve(&[1, 0, 1]), Some(vec![1, 0]));
+    }
+}
@@ -0,0 +1,412 @@
+use alloc::vec::Vec;
+
+use crate::gf2_vector::Gf2Vector;
+use crate::matrix::{MatrixCommon, Number};
+use crate::packed_gf2_matrix::PackedGF2Matrix;
+use crate::GF2Matrix;
+
+/// Returns the index of the first one of a nonzero vector.
+pub(crate) fn pivot(v: &Gf2Vector) -> usize {
+    v.ones().next().expect("zero vectors have no pivot")
+}
+
+/// Brings `vectors` to reduced row echelon form and drops the zero rows.
+///
+/// The rows of the result are sorted by pivot, and each pivot is the only one
+/// in its column.
+pub(crate) fn echelon_basis(vectors: impl IntoIterator<Item = Gf2Vector>) -> Vec<Gf2Vector> {
+    let mut basis: Vec<Gf2Vector> = Vec::new();
+    for mut v in vectors {
+        for b in &basis {
+            if v.get(pivot(b)) == 1 {
+                v ^= b;
+            }
+        }
+        if v.is_zero() {
+            continue;
+        }
+        let p = pivot(&v);
+        for b in basis.iter_mut() {
+            if b.get(p) == 1 {
+                *b ^= &v;
+            }
+        }
+        basis.push(v);
+    }
+    basis.sort_by_key(pivot);
+    basis
+}
+
+/// Reduces `v` modulo the span of `basis`, which must be in reduced row echelon
+/// form.
+fn reduce(basis: &[Gf2Vector], v: &Gf2Vector) -> Gf2Vector {
+    let mut v = v.clone();
+    for b in basis {
+        if v.get(pivot(b)) == 1 {
+            v ^= b;
+        }
+    }
+    v
+}
+
+/// A linear subspace of `GF(2)^n`.
+///
+/// The subspace is stored by a basis in reduced row echelon form, which is
+/// unique, so two subspaces are equal exactly when their bases are. Subspaces
+/// are usually built from the [`kernel`](crate::matrix::MatrixTrait::kernel)
+/// or [`image`](crate::matrix::MatrixTrait::image) of a matrix, with
+/// [`GF2Matrix::kernel_subspace`] and [`GF2Matrix::image_subspace`] or their
+/// [`PackedGF2Matrix`] counterparts.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::gf2_vector::Gf2Vector;
+/// # use lin_algebra::subspace::Subspace;
+/// let u = Subspace::from_rows(3, &[vec![1, 1, 0]]);
+/// let w = Subspace::from_rows(3, &[vec![0, 1, 1]]);
+///
+/// let sum = u.sum(&w);
+/// assert_eq!(sum.dim(), 2);
+/// assert!(sum.contains(&Gf2Vector::from_bits(&[1, 0, 1])));
+/// assert_eq!(u.intersection(&w), Subspace::zero(3));
+/// assert_eq!(sum.orthogonal_complement(), Subspace::from_rows(3, &[vec![1, 1, 1]]));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Subspace {
+    ambient_dim: usize,
+    basis: Vec<Gf2Vector>,
+}
+
+impl Subspace {
+    /// Returns the zero subspace of `GF(2)^n`.
+    pub fn zero(n: usize) -> Self {
+        Self {
+            ambient_dim: n,
+            basis: Vec::new(),
+        }
+    }
+
+    /// Returns the whole space `GF(2)^n`.
+    pub fn full(n: usize) -> Self {
+        Self {
+            ambient_dim: n,
+            basis: (0..n).map(|i| Gf2Vector::unit(n, i)).collect(),
+        }
+    }
+
+    /// Returns the subspace of `GF(2)^n` spanned by `vectors`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a vector does not have length `n`.
+    pub fn span(n: usize, vectors: &[Gf2Vector]) -> Self {
+        assert!(
+            vectors.iter().all(|v| v.len() == n),
+            "every vector must have length {}",
+            n
+        );
+        Self {
+            ambient_dim: n,
+            basis: echelon_basis(vectors.iter().cloned()),
+        }
+    }
+
+    /// Returns the subspace of `GF(2)^n` spanned by rows of `0` and `1`, such
+    /// as the result of [`MatrixTrait::kernel`](crate::matrix::MatrixTrait::kernel)
+    /// for a [`GF2Matrix`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a row does not have length `n`.
+    pub fn from_rows(n: usize, rows: &[Vec<u8>]) -> Self {
+        let vectors: Vec<Gf2Vector> = rows.iter().map(|r| Gf2Vector::from_bits(r)).collect();
+        Self::span(n, &vectors)
+    }
+
+    /// Returns the subspace of `GF(2)^n` spanned by packed rows, such as the
+    /// result of [`PackedGF2Matrix::kernel`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is larger than the width of `T`.
+    pub fn from_packed<T: Number>(n: usize, rows: &[T]) -> Self {
+        let vectors: Vec<Gf2Vector> = rows.iter().map(|&r| Gf2Vector::from_packed(r, n)).collect();
+        Self::span(n, &vectors)
+    }
+
+    /// Returns the dimension `n` of the ambient space `GF(2)^n`.
+    pub fn ambient_dim(&self) -> usize {
+        self.ambient_dim
+    }
+
+    /// Returns the dimension of the subspace.
+    pub fn dim(&self) -> usize {
+        self.basis.len()
+    }
+
+    /// Returns the basis of the subspace, in reduced row echelon form.
+    pub fn basis(&self) -> &[Gf2Vector] {
+        &self.basis
+    }
+
+    /// Returns the basis as the rows of a matrix with [`Self::ambient_dim`]
+    /// columns.
+    ///
+    /// A [`GF2Matrix`] without rows has no columns, so the matrix of the zero
+    /// subspace is `0 x 0` and the ambient dimension is lost: use
+    /// [`Self::basis`] together with [`Self::ambient_dim`] when it matters.
+    pub fn to_matrix(&self) -> GF2Matrix {
+        GF2Matrix::new(self.basis.iter().map(Gf2Vector::to_bits).collect())
+    }
+
+    fn check_ambient(&self, other: &Subspace) {
+        assert_eq!(
+            self.ambient_dim, other.ambient_dim,
+            "subspaces must lie in the same ambient space"
+        );
+    }
+
+    /// Returns `true` if `v` belongs to the subspace.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` does not have length [`Self::ambient_dim`].
+    pub fn contains(&self, v: &Gf2Vector) -> bool {
+        assert_eq!(
+            v.len(),
+            self.ambient_dim,
+            "vector length must equal the ambient dimension"
+        );
+        reduce(&self.basis, v).is_zero()
+    }
+
+    /// Returns `true` if this subspace is contained in `other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ambient spaces differ.
+    pub fn is_subspace_of(&self, other: &Subspace) -> bool {
+        self.check_ambient(other);
+        self.basis.iter().all(|v| other.contains(v))
+    }
+
+    /// Returns the sum `U + W`, the smallest subspace containing both.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ambient spaces differ.
+    pub fn sum(&self, other: &Subspace) -> Subspace {
+        self.check_ambient(other);
+        Subspace {
+            ambient_dim: self.ambient_dim,
+            basis: echelon_basis(self.basis.iter().chain(&other.basis).cloned()),
+        }
+    }
+
+    /// Returns the intersection `U ∩ W`, with the Zassenhaus algorithm.
+    ///
+    /// The rows `[u | u]` for the basis vectors `u` of `U` and `[w | 0]` for
+    /// those of `W` are brought to echelon form. The rows whose left half
+    /// vanishes then have right halves forming a basis of `U ∩ W`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ambient spaces differ.
+    pub fn intersection(&self, other: &Subspace) -> Subspace {
+        self.check_ambient(other);
+        let n = self.ambient_dim;
+        let zero = Gf2Vector::zeros(n);
+        let rows = self
+            .basis
+            .iter()
+            .map(|u| u.concat(u))
+            .chain(other.basis.iter().map(|w| w.concat(&zero)));
+
+        let intersection: Vec<Gf2Vector> = echelon_basis(rows)
+            .into_iter()
+            .filter(|row| pivot(row) >= n)
+            .map(|row| row.slice(n..2 * n))
+            .collect();
+        Subspace::span(n, &intersection)
+    }
+
+    /// Returns the orthogonal complement, the vectors whose dot product with
+    /// every vector of the subspace is zero.
+    ///
+    /// Over GF(2) a subspace can meet its complement, but their dimensions
+    /// always add up to the ambient dimension.
+    pub fn orthogonal_complement(&self) -> Subspace {
+        let n = self.ambient_dim;
+        let pivots: Vec<usize> = self.basis.iter().map(pivot).collect();
+        let mut is_pivot = alloc::vec![false; n];
+        for &p in &pivots {
+            is_pivot[p] = true;
+        }
+
+        // One kernel vector of the basis matrix per free column, as for the
+        // kernel of a matrix in reduced echelon form.
+        let complement = (0..n).filter(|&col| !is_pivot[col]).map(|free| {
+            let mut v = Gf2Vector::unit(n, free);
+            for (b, &p) in self.basis.iter().zip(&pivots) {
+                if b.get(free) == 1 {
+                    v.flip(p);
+                }
+            }
+            v
+        });
+        Subspace {
+            ambient_dim: n,
+            basis: echelon_basis(complement),
+        }
+    }
+
+    /// Returns representatives of a basis of the quotient space `V / W`, where
+    /// `V` is this subspace and `W` is `sub`.
+    ///
+    /// The representatives are vectors of `V` whose cosets modulo `W` are
+    /// linearly independent and span `V / W`, so there are
+    /// `self.dim() - sub.dim()` of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sub` is not a subspace of this subspace.
+    pub fn quotient_representatives(&self, sub: &Subspace) -> Vec<Gf2Vector> {
+        assert!(
+            sub.is_subspace_of(self),
+            "the quotient needs a subspace of this subspace"
+        );
+        let mut reduced = sub.basis.clone();
+        let mut representatives = Vec::new();
+        for v in &self.basis {
+            let r = reduce(&reduced, v);
+            if !r.is_zero() {
+                representatives.push(v.clone());
+                reduced = echelon_basis(reduced.into_iter().chain(core::iter::once(r)));
+            }
+        }
+        representatives
+    }
+
+    /// Returns the class of `v` modulo this subspace, as the canonical
+    /// representative of `v + W`: two vectors are in the same coset exactly
+    /// when they have the same representative.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` does not have length [`Self::ambient_dim`].
+    pub fn coset_representative(&self, v: &Gf2Vector) -> Gf2Vector {
+        assert_eq!(
+            v.len(),
+            self.ambient_dim,
+            "vector length must equal the ambient dimension"
+        );
+        reduce(&self.basis, v)
+    }
+}
+
+impl GF2Matrix {
+    /// Returns the kernel as a [`Subspace`] of `GF(2)^ncols`.
+    pub fn kernel_subspace(&self) -> Subspace {
+        Subspace::span(self.ncols(), &self.kernel_vectors())
+    }
+
+    /// Returns the image (the row space) as a [`Subspace`] of `GF(2)^ncols`.
+    pub fn image_subspace(&self) -> Subspace {
+        Subspace::span(self.ncols(), &self.image_vectors())
+    }
+}
+
+impl<T: Number> PackedGF2Matrix<T> {
+    /// Returns the kernel as a [`Subspace`] of `GF(2)^ncols`.
+    pub fn kernel_subspace(&self) -> Subspace {
+        Subspace::from_packed(self.ncols(), &self.kernel())
+    }
+
+    /// Returns the image (the row space) as a [`Subspace`] of `GF(2)^ncols`.
+    pub fn image_subspace(&self) -> Subspace {
+        Subspace::from_packed(self.ncols(), &self.image())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_rng::splitmix64;
+
+    /// Returns `count` vectors of length `n` from a splitmix64 sequence, with
+    /// entries equal to one with probability 1/4.
+    fn random_vectors(seed: &mut u64, n: usize, count: usize) -> Vec<Gf2Vector> {
+        (0..count)
+            .map(|_| {
+                let bits: Vec<u8> = (0..n)
+                    .map(|_| splitmix64(seed).is_multiple_of(4) as u8)
+                    .collect();
+                Gf2Vector::from_bits(&bits)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dimension_formulas() {
+        let mut seed = 7;
+        for n in [5, 12, 70] {
+            for _ in 0..10 {
+                let common = random_vectors(&mut seed, n, 2);
+                let mut a = random_vectors(&mut seed, n, n / 3);
+                let mut b = random_vectors(&mut seed, n, n / 2);
+                a.extend(common.iter().cloned());
+                b.extend(common);
+                let (u, w) = (Subspace::span(n, &a), Subspace::span(n, &b));
+
+                let sum = u.sum(&w);
+                let intersection = u.intersection(&w);
+                assert_eq!(sum.dim() + intersection.dim(), u.dim() + w.dim());
+                assert!(intersection.is_subspace_of(&u) && intersection.is_subspace_of(&w));
+                assert!(u.is_subspace_of(&sum) && w.is_subspace_of(&sum));
+
+                let complement = u.orthogonal_complement();
+                assert_eq!(complement.dim() + u.dim(), n);
+                for x in complement.basis() {
+                    assert!(u.basis().iter().all(|y| x.dot(y) == 0));
+                }
+                assert_eq!(complement.orthogonal_complement(), u);
+
+                let representatives = sum.quotient_representatives(&u);
+                assert_eq!(representatives.len(), sum.dim() - u.dim());
+                assert_eq!(u.sum(&Subspace::span(n, &representatives)), sum);
+            }
+        }
+    }
+
+    #[test]
+    fn test_kernel_and_image_subspaces() {
+        let dense = GF2Matrix::new(vec![
+            vec![1, 1, 0, 1, 0],
+            vec![0, 1, 1, 0, 0],
+            vec![1, 0, 1, 1, 0],
+        ]);
+        let packed = PackedGF2Matrix::<u8>::from(&dense);
+
+        let kernel = dense.kernel_subspace();
+        let image = dense.image_subspace();
+        assert_eq!(packed.kernel_subspace(), kernel);
+        assert_eq!(packed.image_subspace(), image);
+        assert_eq!(kernel.dim() + image.dim(), 5);
+        // The kernel is the orthogonal complement of the row space.
+        assert_eq!(image.orthogonal_complement(), kernel);
+
+        let v = Gf2Vector::from_bits(&[1, 0, 1, 1, 1]);
+        let shifted = &v ^ &image.basis()[0];
+        assert!(!image.contains(&v));
+        assert_eq!(
+            image.coset_representative(&v),
+            image.coset_representative(&shifted)
+        );
+        assert_eq!(Subspace::full(5).quotient_representatives(&image).len(), 3);
+
+        assert_eq!(image.to_matrix().ncols(), 5);
+        // Without rows, the matrix of the zero subspace has no columns.
+        assert_eq!(Subspace::zero(5).to_matrix().ncols(), 0);
+    }
+}
@@ -0,0 +1,34 @@
+//! Deterministic generators for the test fixtures.
+
+pub(crate) use crate::iterative_solvers::splitmix64;
+use crate::sparse_gf2_matrix::SparseGF2Matrix;
+
+/// Advances an xorshift64 state and returns it.
+///
+/// The map is linear over GF(2), so its outputs span at most 64 dimensions:
+/// use [`splitmix64`] when the test needs more independent bits than that.
+pub(crate) fn xorshift64(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+/// Returns a sparse matrix whose rows each hold `per_row` random column
+/// indices, repeated ones cancelling.
+pub(crate) fn random_sparse(
+    nrows: usize,
+    ncols: usize,
+    per_row: usize,
+    seed: u64,
+) -> SparseGF2Matrix {
+    let mut state = seed;
+    let rows = (0..nrows)
+        .map(|_| {
+            (0..per_row)
+                .map(|_| (splitmix64(&mut state) % ncols as u64) as usize)
+                .collect()
+        })
+        .collect();
+    SparseGF2Matrix::from_rows(ncols, rows)
+}
@@ -0,0 +1,485 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::matrix::Number;
+use crate::packed_gf2_matrix::PackedGF2Matrix;
+use crate::GF2Matrix;
+
+/// Entry access to a matrix over GF(2), implemented by [`GF2Matrix`] and
+/// [`PackedGF2Matrix`] so that views work on both.
+pub trait GF2Storage {
+    /// Returns the number of rows and columns.
+    fn shape(&self) -> (usize, usize);
+
+    /// Returns the entry at `(row, col)`, `0` or `1`.
+    fn get(&self, row: usize, col: usize) -> u8;
+}
+
+/// Mutable entry and row-segment access to a matrix over GF(2).
+///
+/// Row operations act on the columns `cols` only, so that a window can be
+/// transformed without touching the rest of the matrix.
+pub trait GF2StorageMut: GF2Storage {
+    /// Sets the entry at `(row, col)` to `value`, `0` or `1`.
+    fn set(&mut self, row: usize, col: usize, value: u8);
+
+    /// Swaps the entries of rows `a` and `b` in the columns `cols`.
+    fn swap_row_segments(&mut self, a: usize, b: usize, cols: Range<usize>);
+
+    /// Adds row `source` to row `target` in the columns `cols`.
+    fn add_row_segment(&mut self, target: usize, source: usize, cols: Range<usize>);
+}
+
+/// A borrowed rectangular window of a GF(2) matrix.
+///
+/// Views are cheap to create and to narrow down: they only store the borrowed
+/// matrix and the row and column ranges of the window. All indices are
+/// relative to the window.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::gf2_matrix::GF2Matrix;
+/// let m = GF2Matrix::new(vec![vec![1, 0, 1, 1], vec![0, 1, 1, 0], vec![1, 1, 0, 0]]);
+///
+/// let window = m.window(1..3, 1..4);
+/// assert_eq!(window.row(0), vec![1, 1, 0]);
+/// assert_eq!(window.column(2), vec![0, 0]);
+/// assert_eq!(window.col_range(1..3).get(1, 0), 0);
+/// ```
+#[derive(Debug)]
+pub struct MatrixView<'a, M: GF2Storage + ?Sized> {
+    matrix: &'a M,
+    rows: Range<usize>,
+    cols: Range<usize>,
+}
+
+/// A mutably borrowed rectangular window of a GF(2) matrix.
+///
+/// In-place operations only modify the entries inside the window.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::packed_gf2_matrix::PackedGF2Matrix;
+/// let mut m = PackedGF2Matrix::new(vec![0b1011u8, 0b0110, 0b1100], 4);
+///
+/// // Row-reduce the lower right 2 x 3 block only.
+/// let pivots = m.window_mut(1..3, 1..4).echelon_form_in_place();
+///
+/// assert_eq!(pivots, vec![0, 1]);
+/// assert_eq!(m, PackedGF2Matrix::new(vec![0b1011, 0b0100, 0b1010], 4));
+/// ```
+#[derive(Debug)]
+pub struct MatrixViewMut<'a, M: GF2StorageMut + ?Sized> {
+    matrix: &'a mut M,
+    rows: Range<usize>,
+    cols: Range<usize>,
+}
+
+/// Returns the sub-range `sub` of `range`, given relative to its start.
+fn sub_range(range: &Range<usize>, sub: Range<usize>) -> Range<usize> {
+    assert!(
+        sub.start <= sub.end && sub.end <= range.len(),
+        "range {:?} out of bounds for a window of size {}",
+        sub,
+        range.len()
+    );
+    range.start + sub.start..range.start + sub.end
+}
+
+impl<'a, M: GF2Storage + ?Sized> MatrixView<'a, M> {
+    /// Creates a view of the window `rows` x `cols` of `matrix`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the window does not fit in the matrix.
+    pub fn new(matrix: &'a M, rows: Range<usize>, cols: Range<usize>) -> Self {
+        let (nrows, ncols) = matrix.shape();
+        let rows = sub_range(&(0..nrows), rows);
+        let cols = sub_range(&(0..ncols), cols);
+        Self { matrix, rows, cols }
+    }
+
+    /// Returns the number of rows of the window.
+    pub fn nrows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the number of columns of the window.
+    pub fn ncols(&self) -> usize {
+        self.cols.len()
+    }
+
+    /// Returns the entry at `(row, col)` of the window.
+    pub fn get(&self, row: usize, col: usize) -> u8 {
+        assert!(
+            row < self.nrows() && col < self.ncols(),
+            "index out of bounds"
+        );
+        self.matrix
+            .get(self.rows.start + row, self.cols.start + col)
+    }
+
+    /// Returns the view of the rows `rows` of this window.
+    pub fn row_range(&self, rows: Range<usize>) -> MatrixView<'a, M> {
+        self.window(rows, 0..self.ncols())
+    }
+
+    /// Returns the view of the columns `cols` of this window.
+    pub fn col_range(&self, cols: Range<usize>) -> MatrixView<'a, M> {
+        self.window(0..self.nrows(), cols)
+    }
+
+    /// Returns the view of the sub-window `rows` x `cols` of this window.
+    pub fn window(&self, rows: Range<usize>, cols: Range<usize>) -> MatrixView<'a, M> {
+        MatrixView {
+            matrix: self.matrix,
+            rows: sub_range(&self.rows, rows),
+            cols: sub_range(&self.cols, cols),
+        }
+    }
+
+    /// Returns a copy of row `row` of the window.
+    pub fn row(&self, row: usize) -> Vec<u8> {
+        (0..self.ncols()).map(|j| self.get(row, j)).collect()
+    }
+
+    /// Returns a copy of column `col` of the window.
+    pub fn column(&self, col: usize) -> Vec<u8> {
+        (0..self.nrows()).map(|i| self.get(i, col)).collect()
+    }
+
+    /// Returns `true` if every entry of the window is zero.
+    pub fn is_zero(&self) -> bool {
+        (0..self.nrows()).all(|i| (0..self.ncols()).all(|j| self.get(i, j) == 0))
+    }
+
+    /// Copies the window into a new [`GF2Matrix`].
+    pub fn to_gf2_matrix(&self) -> GF2Matrix {
+        GF2Matrix::new((0..self.nrows()).map(|i| self.row(i)).collect())
+    }
+
+    /// Copies the window into a new [`PackedGF2Matrix`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the window has more columns than bits in `T`.
+    pub fn to_packed<T: Number>(&self) -> PackedGF2Matrix<T> {
+        let ncols = self.ncols();
+        assert!(
+            ncols <= core::mem::size_of::<T>() * 8,
+            "{} columns do not fit in the packed type",
+            ncols
+        );
+        let rows = (0..self.nrows())
+            .map(|i| {
+                (0..ncols).fold(T::zero(), |acc, j| {
+                    (acc << 1)
+                        ^ if self.get(i, j) == 1 {
+                            T::one()
+                        } else {
+                            T::zero()
+                        }
+                })
+            })
+            .collect();
+        PackedGF2Matrix::new(rows, ncols)
+    }
+}
+
+impl<M: GF2Storage + ?Sized> Clone for MatrixView<'_, M> {
+    fn clone(&self) -> Self {
+        Self {
+            matrix: self.matrix,
+            rows: self.rows.clone(),
+            cols: self.cols.clone(),
+        }
+    }
+}
+
+impl<'a, M: GF2StorageMut + ?Sized> MatrixViewMut<'a, M> {
+    /// Creates a mutable view of the window `rows` x `cols` of `matrix`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the window does not fit in the matrix.
+    pub fn new(matrix: &'a mut M, rows: Range<usize>, cols: Range<usize>) -> Self {
+        let (nrows, ncols) = matrix.shape();
+        let rows = sub_range(&(0..nrows), rows);
+        let cols = sub_range(&(0..ncols), cols);
+        Self { matrix, rows, cols }
+    }
+
+    /// Returns a read-only view of the window.
+    pub fn as_view(&self) -> MatrixView<'_, M> {
+        MatrixView {
+            matrix: &*self.matrix,
+            rows: self.rows.clone(),
+            cols: self.cols.clone(),
+        }
+    }
+
+    /// Returns the number of rows of the window.
+    pub fn nrows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the number of columns of the window.
+    pub fn ncols(&self) -> usize {
+        self.cols.len()
+    }
+
+    /// Returns the entry at `(row, col)` of the window.
+    pub fn get(&self, row: usize, col: usize) -> u8 {
+        self.as_view().get(row, col)
+    }
+
+    /// Sets the entry at `(row, col)` of the window to `value`.
+    pub fn set(&mut self, row: usize, col: usize, value: u8) {
+        assert!(
+            row < self.nrows() && col < self.ncols(),
+            "index out of bounds"
+        );
+        assert!(value <= 1, "GF(2) entries must be 0 or 1");
+        self.matrix
+            .set(self.rows.start + row, self.cols.start + col, value);
+    }
+
+    /// Returns the mutable view of the rows `rows` of this window.
+    pub fn row_range_mut(&mut self, rows: Range<usize>) -> MatrixViewMut<'_, M> {
+        let ncols = self.ncols();
+        self.window_mut(rows, 0..ncols)
+    }
+
+    /// Returns the mutable view of the columns `cols` of this window.
+    pub fn col_range_mut(&mut self, cols: Range<usize>) -> MatrixViewMut<'_, M> {
+        let nrows = self.nrows();
+        self.window_mut(0..nrows, cols)
+    }
+
+    /// Returns the mutable view of the sub-window `rows` x `cols` of this window.
+    pub fn window_mut(&mut self, rows: Range<usize>, cols: Range<usize>) -> MatrixViewMut<'_, M> {
+        MatrixViewMut {
+            rows: sub_range(&self.rows, rows),
+            cols: sub_range(&self.cols, cols),
+            matrix: &mut *self.matrix,
+        }
+    }
+
+    /// Swaps rows `a` and `b` of the window.
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        assert!(
+            a < self.nrows() && b < self.nrows(),
+            "row index out of bounds"
+        );
+        if a != b {
+            self.matrix.swap_row_segments(
+                self.rows.start + a,
+                self.rows.start + b,
+                self.cols.clone(),
+            );
+        }
+    }
+
+    /// Adds row `source` of the window to row `target`.
+    pub fn add_row(&mut self, target: usize, source: usize) {
+        assert!(
+            target < self.nrows() && source < self.nrows(),
+            "row index out of bounds"
+        );
+        assert_ne!(target, source, "cannot add a row to itself");
+        self.matrix.add_row_segment(
+            self.rows.start + target,
+            self.rows.start + source,
+            self.cols.clone(),
+        );
+    }
+
+    /// Sets every entry of the window to `value`.
+    pub fn fill(&mut self, value: u8) {
+        for i in 0..self.nrows() {
+            for j in 0..self.ncols() {
+                self.set(i, j, value);
+            }
+        }
+    }
+
+    /// Copies the entries of `source`, which must have the shape of the window,
+    /// into the window.
+    pub fn copy_from<N: GF2Storage + ?Sized>(&mut self, source: &MatrixView<'_, N>) {
+        assert_eq!(
+            (source.nrows(), source.ncols()),
+            (self.nrows(), self.ncols()),
+            "source and window shapes differ"
+        );
+        for i in 0..self.nrows() {
+            for j in 0..self.ncols() {
+                self.set(i, j, source.get(i, j));
+            }
+        }
+    }
+
+    /// Reduces the window in place to reduced row echelon form.
+    ///
+    /// # Returns
+    ///
+    /// The pivot column of each nonzero row of the reduced window, relative to
+    /// the window; their number is the rank of the window.
+    pub fn echelon_form_in_place(&mut self) -> Vec<usize> {
+        let mut pivots = Vec::new();
+        let mut r = 0;
+        for col in 0..self.ncols() {
+            if r == self.nrows() {
+                break;
+            }
+            let Some(i) = (r..self.nrows()).find(|&i| self.get(i, col) == 1) else {
+                continue;
+            };
+            self.swap_rows(r, i);
+            for i in 0..self.nrows() {
+                if i != r && self.get(i, col) == 1 {
+                    self.add_row(i, r);
+                }
+            }
+            pivots.push(col);
+            r += 1;
+        }
+        pivots
+    }
+}
+
+impl GF2Matrix {
+    /// Returns a view of the whole matrix.
+    pub fn view(&self) -> MatrixView<'_, GF2Matrix> {
+        let (nrows, ncols) = self.shape();
+        MatrixView::new(self, 0..nrows, 0..ncols)
+    }
+
+    /// Returns a view of the window `rows` x `cols` of the matrix.
+    pub fn window(&self, rows: Range<usize>, cols: Range<usize>) -> MatrixView<'_, GF2Matrix> {
+        MatrixView::new(self, rows, cols)
+    }
+
+    /// Returns a mutable view of the whole matrix.
+    pub fn view_mut(&mut self) -> MatrixViewMut<'_, GF2Matrix> {
+        let (nrows, ncols) = self.shape();
+        MatrixViewMut::new(self, 0..nrows, 0..ncols)
+    }
+
+    /// Returns a mutable view of the window `rows` x `cols` of the matrix.
+    pub fn window_mut(
+        &mut self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> MatrixViewMut<'_, GF2Matrix> {
+        MatrixViewMut::new(self, rows, cols)
+    }
+}
+
+impl<T: Number> PackedGF2Matrix<T> {
+    /// Returns a view of the whole matrix.
+    pub fn view(&self) -> MatrixView<'_, PackedGF2Matrix<T>> {
+        MatrixView::new(self, 0..self.nrows(), 0..self.ncols())
+    }
+
+    /// Returns a view of the window `rows` x `cols` of the matrix.
+    pub fn window(
+        &self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> MatrixView<'_, PackedGF2Matrix<T>> {
+        MatrixView::new(self, rows, cols)
+    }
+
+    /// Returns a mutable view of the whole matrix.
+    pub fn view_mut(&mut self) -> MatrixViewMut<'_, PackedGF2Matrix<T>> {
+        let (nrows, ncols) = (self.nrows(), self.ncols());
+        MatrixViewMut::new(self, 0..nrows, 0..ncols)
+    }
+
+    /// Returns a mutable view of the window `rows` x `cols` of the matrix.
+    pub fn window_mut(
+        &mut self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> MatrixViewMut<'_, PackedGF2Matrix<T>> {
+        MatrixViewMut::new(self, rows, cols)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_views_agree_on_both_representations() {
+        let dense = GF2Matrix::new(vec![
+            vec![1, 0, 1, 1, 0],
+            vec![0, 1, 1, 0, 1],
+            vec![1, 1, 0, 0, 1],
+            vec![0, 0, 1, 1, 1],
+        ]);
+        let packed = dense.view().to_packed::<u8>();
+
+        let a = dense.window(1..4, 2..5);
+        let b = packed.window(1..4, 2..5);
+        assert_eq!(a.to_gf2_matrix().elements, b.to_gf2_matrix().elements);
+        assert_eq!(
+            a.to_packed::<u8>(),
+            PackedGF2Matrix::new(vec![0b101, 0b001, 0b111], 3)
+        );
+        assert_eq!(a.row_range(1..3).column(0), vec![0, 1]);
+        assert_eq!(b.col_range(1..2).row_range(2..3).get(0, 0), 1);
+        assert!(dense.window(0..2, 0..0).is_zero());
+        assert!(!packed.view().is_zero());
+    }
+
+    #[test]
+    fn test_mutable_windows_only_touch_the_window() {
+        let original = GF2Matrix::new(vec![vec![1, 1, 1, 1], vec![1, 0, 1, 0], vec![0, 1, 1, 0]]);
+
+        let mut dense = original.clone();
+        let mut packed = original.view().to_packed::<u16>();
+        fn transform<M: GF2StorageMut>(mut window: MatrixViewMut<'_, M>) {
+            window.swap_rows(0, 1);
+            window.add_row(1, 0);
+            window.set(0, 0, 0);
+        }
+        transform(dense.window_mut(1..3, 1..3));
+        transform(packed.window_mut(1..3, 1..3));
+        let expected = GF2Matrix::new(vec![vec![1, 1, 1, 1], vec![1, 0, 1, 0], vec![0, 1, 0, 0]]);
+        assert_eq!(dense.elements, expected.elements);
+        assert_eq!(GF2Matrix::from(&packed).elements, expected.elements);
+
+        packed.window_mut(0..1, 2..4).fill(0);
+        assert_eq!(packed.row(0), 0b1100);
+        dense
+            .window_mut(2..3, 0..4)
+            .copy_from(&packed.window(0..1, 0..4));
+        assert_eq!(dense.elements[2], vec![1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_window_echelon_form() {
+        let mut m = GF2Matrix::new(vec![
+            vec![1, 1, 0, 1, 1],
+            vec![1, 0, 1, 1, 0],
+            vec![1, 1, 1, 0, 1],
+            vec![1, 1, 0, 1, 0],
+        ]);
+        let mut window = m.window_mut(1..4, 1..5);
+        let pivots = window.echelon_form_in_place();
+        assert_eq!(pivots, vec![0, 1, 3]);
+        assert_eq!(
+            window.as_view().to_gf2_matrix().elements,
+            vec![vec![1, 0, 1, 0], vec![0, 1, 1, 0], vec![0, 0, 0, 1]]
+        );
+        // The first row and column are untouched.
+        assert_eq!(m.elements[0], vec![1, 1, 0, 1, 1]);
+        assert_eq!(
+            (1..4).map(|i| m.elements[i][0]).collect::<Vec<_>>(),
+            vec![1, 1, 1]
+        );
+    }
+}
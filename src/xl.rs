@@ -0,0 +1,478 @@
+use crate::boolean_poly::{BooleanPolynomial, Monomial, MonomialOrder};
+use crate::matrix::Number;
+use crate::packed_gf2_matrix::PackedGF2Matrix;
+use crate::sparse_gf2_matrix::dense_rref;
+use alloc::{vec, vec::Vec};
+
+/// Builds the Macaulay matrix of a system of boolean polynomials at a given
+/// degree.
+///
+/// The rows are the products `m * f` of every polynomial `f` with every monomial
+/// `m` in `nvars` variables of degree at most `degree - deg(f)`; the columns are
+/// the monomials appearing in those products, in decreasing `order`. Column `0`
+/// is the largest monomial, so the echelon form of the matrix exposes leading
+/// monomials first.
+///
+/// # Arguments
+///
+/// * `polys` - the polynomials of the system.
+/// * `nvars` - the number of variables `x_0, ..., x_{nvars-1}`.
+/// * `degree` - the degree `D` of the Macaulay matrix.
+/// * `order` - the monomial order of the columns.
+///
+/// # Returns
+///
+/// The bit-packed Macaulay matrix and the monomial of each column.
+///
+/// # Panics
+///
+/// Panics if there are more columns than bits in `T`, or if `degree` is below
+/// the degree of a polynomial.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::boolean_poly::{BooleanPolynomial, Monomial, MonomialOrder};
+/// # use lin_algebra::xl::macaulay_matrix;
+/// // x0*x1 + x0 + 1 in two variables, at degree 3: rows f, x0*f and x1*f.
+/// let f = BooleanPolynomial::from_monomials(&[
+///     Monomial::from_variables(&[0, 1]),
+///     Monomial::variable(0),
+///     Monomial::one(),
+/// ]);
+/// let (m, columns) = macaulay_matrix::<u8>(&[f], 2, 3, MonomialOrder::DegRevLex);
+///
+/// assert_eq!(columns.len(), 4);
+/// assert_eq!(m.nrows(), 3);
+/// // x0*f = x0*x1 + x0 + x0 = x0*x1
+/// assert_eq!(m.row(1), 0b1000);
+/// ```
+pub fn macaulay_matrix<T: Number>(
+    polys: &[BooleanPolynomial],
+    nvars: usize,
+    degree: usize,
+    order: MonomialOrder,
+) -> (PackedGF2Matrix<T>, Vec<Monomial>) {
+    let rows = macaulay_rows(polys, nvars, degree);
+    let columns = column_monomials(&rows, order);
+    assert!(
+        columns.len() <= core::mem::size_of::<T>() * 8,
+        "{} monomials do not fit in the packed type",
+        columns.len()
+    );
+
+    let ncols = columns.len();
+    let packed = index_rows(&rows, &columns)
+        .iter()
+        .map(|row| {
+            row.iter()
+                .fold(T::zero(), |acc, &j| acc ^ (T::one() << (ncols - 1 - j)))
+        })
+        .collect();
+    (PackedGF2Matrix::new(packed, ncols), columns)
+}
+
+/// Solves a system of linear boolean equations (XOR-SAT).
+///
+/// # Arguments
+///
+/// * `equations` - polynomials of degree at most one, each meaning `p = 0`.
+/// * `nvars` - the number of variables.
+///
+/// # Returns
+///
+/// `None` if the system is inconsistent, and otherwise a particular solution
+/// (with all free variables set to zero) together with a basis of the
+/// solutions of the homogeneous system; the solutions are the particular
+/// solution plus any combination of the basis.
+///
+/// # Panics
+///
+/// Panics if an equation has degree larger than one or uses a variable beyond
+/// `nvars`.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::boolean_poly::BooleanPolynomial;
+/// # use lin_algebra::xl::xor_sat;
+/// let x = BooleanPolynomial::variable;
+/// // x0 + x1 = 1, x1 + x2 = 0
+/// let equations = [&x(0) + &(&x(1) + &BooleanPolynomial::one()), &x(1) + &x(2)];
+///
+/// let (particular, basis) = xor_sat(&equations, 3).unwrap();
+/// assert_eq!(particular, vec![1, 0, 0]);
+/// assert_eq!(basis, vec![vec![1, 1, 1]]);
+/// ```
+pub fn xor_sat(equations: &[BooleanPolynomial], nvars: usize) -> Option<(Vec<u8>, Vec<Vec<u8>>)> {
+    for e in equations {
+        assert!(
+            e.degree().unwrap_or(0) <= 1,
+            "XOR-SAT equations must be linear"
+        );
+        assert!(
+            e.nvars() <= nvars,
+            "equation uses more than nvars variables"
+        );
+    }
+
+    let rows = echelonise(equations, MonomialOrder::DegRevLex);
+    if rows
+        .iter()
+        .any(|r| r.leading_monomial(MonomialOrder::DegRevLex) == Some(Monomial::one()))
+    {
+        return None;
+    }
+
+    let pivots: Vec<usize> = rows
+        .iter()
+        .map(|r| {
+            r.leading_monomial(MonomialOrder::DegRevLex)
+                .unwrap()
+                .variables()[0]
+        })
+        .collect();
+
+    let mut particular = vec![0u8; nvars];
+    for (row, &p) in rows.iter().zip(&pivots) {
+        particular[p] = row.contains(&Monomial::one()) as u8;
+    }
+
+    let basis = (0..nvars)
+        .filter(|v| !pivots.contains(v))
+        .map(|free| {
+            let mut vector = vec![0u8; nvars];
+            vector[free] = 1;
+            for (row, &p) in rows.iter().zip(&pivots) {
+                vector[p] = row.contains(&Monomial::variable(free)) as u8;
+            }
+            vector
+        })
+        .collect();
+    Some((particular, basis))
+}
+
+/// Solves a system of boolean polynomial equations by linearisation.
+///
+/// Every monomial is treated as an independent unknown and the system is
+/// echelonised; the linear equations that appear are solved and the resulting
+/// candidates are checked against the whole echelonised system.
+///
+/// This is [`xl`] without any multiplication of the equations, so it only pays
+/// off for heavily overdetermined systems.
+///
+/// # Returns
+///
+/// All solutions of the system, as vectors of `nvars` bits in lexicographic
+/// order.
+pub fn linearisation(polys: &[BooleanPolynomial], nvars: usize) -> Vec<Vec<u8>> {
+    solve_echelonised(&echelonise(polys, MonomialOrder::DegRevLex), nvars)
+}
+
+/// Solves a system of boolean polynomial equations with the XL (eXtended
+/// Linearisation) algorithm.
+///
+/// The equations are multiplied by all monomials up to `degree`, the resulting
+/// Macaulay matrix is echelonised in degree reverse lexicographic order, and
+/// the linear equations obtained at the bottom of the echelon form are solved.
+/// Each candidate is checked against the whole echelonised system, so the
+/// result is exact for any degree at least that of the system; a larger degree
+/// only makes the linear part larger and the enumeration of candidates
+/// cheaper.
+///
+/// # Arguments
+///
+/// * `polys` - the polynomials of the system, each meaning `p = 0`.
+/// * `nvars` - the number of variables.
+/// * `degree` - the degree of the Macaulay matrix.
+///
+/// # Returns
+///
+/// All solutions of the system, as vectors of `nvars` bits in lexicographic
+/// order.
+///
+/// # Panics
+///
+/// Panics if `degree` is below the degree of a polynomial, whose equation
+/// would otherwise be left out of the Macaulay matrix.
+///
+/// # Example
+///
+/// ```rust
+/// # use lin_algebra::boolean_poly::BooleanPolynomial;
+/// # use lin_algebra::xl::xl;
+/// let x = BooleanPolynomial::variable;
+/// let one = BooleanPolynomial::one();
+/// // x0*x1 + x2 = 0, x1*x2 + x0 + 1 = 0, x0*x2 + x1 = 0
+/// let polys = [
+///     &(&x(0) * &x(1)) + &x(2),
+///     &(&(&x(1) * &x(2)) + &x(0)) + &one,
+///     &(&x(0) * &x(2)) + &x(1),
+/// ];
+///
+/// assert_eq!(xl(&polys, 3, 3), vec![vec![1, 0, 0]]);
+/// ```
+pub fn xl(polys: &[BooleanPolynomial], nvars: usize, degree: usize) -> Vec<Vec<u8>> {
+    let rows = macaulay_rows(polys, nvars, degree);
+    solve_echelonised(&echelonise(&rows, MonomialOrder::DegRevLex), nvars)
+}
+
+/// Returns the reduced row echelon form of a set of polynomials, seen as the
+/// rows of their Macaulay matrix in `order`.
+///
+/// The nonzero rows are returned, sorted by decreasing leading monomial.
+pub(crate) fn echelonise(
+    polys: &[BooleanPolynomial],
+    order: MonomialOrder,
+) -> Vec<BooleanPolynomial> {
+    let columns = column_monomials(polys, order);
+    dense_rref(&index_rows(polys, &columns), columns.len())
+        .iter()
+        .map(|row| {
+            let monomials: Vec<Monomial> = row.iter().map(|&j| columns[j]).collect();
+            BooleanPolynomial::from_monomials(&monomials)
+        })
+        .collect()
+}
+
+/// Enumerates the solutions of an echelonised system in degree order: the
+/// linear rows fix the pivot variables in terms of the free ones, and every
+/// candidate is checked against the nonlinear rows.
+fn solve_echelonised(rows: &[BooleanPolynomial], nvars: usize) -> Vec<Vec<u8>> {
+    assert!(
+        nvars <= 64,
+        "boolean polynomials support at most 64 variables"
+    );
+    let order = MonomialOrder::DegRevLex;
+
+    let mut linear = Vec::new();
+    let mut nonlinear = Vec::new();
+    for row in rows {
+        let lead = row.leading_monomial(order).unwrap();
+        match lead.degree() {
+            0 => return Vec::new(),
+            1 => linear.push((lead.bits(), row)),
+            _ => nonlinear.push(row),
+        }
+    }
+
+    let pivot_mask = linear.iter().fold(0u64, |acc, &(p, _)| acc | p);
+    let free: Vec<usize> = (0..nvars).filter(|&v| (pivot_mask >> v) & 1 == 0).collect();
+    assert!(free.len() < 64, "too many free variables to enumerate");
+
+    let mut solutions = Vec::new();
+    for assignment in 0..1u64 << free.len() {
+        let mut point = free
+            .iter()
+            .enumerate()
+            .fold(0u64, |acc, (k, &v)| acc | (((assignment >> k) & 1) << v));
+        // In reduced echelon form the other terms of a linear row only involve
+        // free variables, so x_p = row(x) evaluated with x_p = 0.
+        for &(p, row) in &linear {
+            point |= (row.evaluate_bits(point) as u64) * p;
+        }
+        if nonlinear.iter().all(|row| row.evaluate_bits(point) == 0) {
+            solutions.push(point);
+        }
+    }
+
+    solutions.sort_unstable_by_key(|&point| point.reverse_bits());
+    solutions
+        .into_iter()
+        .map(|point| (0..nvars).map(|v| ((point >> v) & 1) as u8).collect())
+        .collect()
+}
+
+/// Returns the products `m * f` of the Macaulay matrix of degree `degree`.
+///
+/// # Panics
+///
+/// Panics if `degree` is below the degree of a polynomial.
+fn macaulay_rows(
+    polys: &[BooleanPolynomial],
+    nvars: usize,
+    degree: usize,
+) -> Vec<BooleanPolynomial> {
+    let mut rows = Vec::new();
+    for f in polys {
+        let Some(d) = f.degree() else { continue };
+        assert!(
+            d <= degree,
+            "degree {} is below the degree {} of an equation",
+            degree,
+            d
+        );
+        for m in monomials_up_to(nvars, degree - d) {
+            rows.push(f.mul_monomial(m));
+        }
+    }
+    rows
+}
+
+/// Returns all monomials in `nvars` variables of degree at most `degree`.
+fn monomials_up_to(nvars: usize, degree: usize) -> Vec<Monomial> {
+    let mut monomials = vec![Monomial::one()];
+    let mut layer = vec![Monomial::one()];
+    for _ in 0..degree.min(nvars) {
+        let mut next = Vec::new();
+        for m in &layer {
+            // Extend with variables after the last one of m to avoid repeats.
+            let start = 64 - m.bits().leading_zeros() as usize;
+            for v in start..nvars {
+                next.push(m.mul(Monomial::variable(v)));
+            }
+        }
+        monomials.extend_from_slice(&next);
+        layer = next;
+    }
+    monomials
+}
+
+/// Returns the monomials appearing in `polys`, in decreasing `order`.
+fn column_monomials(polys: &[BooleanPolynomial], order: MonomialOrder) -> Vec<Monomial> {
+    let mut columns: Vec<Monomial> = polys.iter().flat_map(|p| p.monomials()).collect();
+    columns.sort_unstable();
+    columns.dedup();
+    order.sort_decreasing(&mut columns);
+    columns
+}
+
+/// Returns the rows of `polys` as sorted lists of column indices.
+fn index_rows(polys: &[BooleanPolynomial], columns: &[Monomial]) -> Vec<Vec<usize>> {
+    let mut index: Vec<(Monomial, usize)> = columns.iter().copied().zip(0..).collect();
+    index.sort_unstable();
+    polys
+        .iter()
+        .map(|p| {
+            let mut row: Vec<usize> = p
+                .monomials()
+                .map(|m| index[index.binary_search_by_key(&m, |&(c, _)| c).unwrap()].1)
+                .collect();
+            row.sort_unstable();
+            row
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_rng::xorshift64;
+
+    /// Builds a random quadratic system with `count` equations vanishing at
+    /// `solution`.
+    fn planted_system(
+        nvars: usize,
+        count: usize,
+        solution: u64,
+        seed: u64,
+    ) -> Vec<BooleanPolynomial> {
+        let mut state = seed;
+        let mut next = || xorshift64(&mut state);
+        let monomials = monomials_up_to(nvars, 2);
+        (0..count)
+            .map(|_| {
+                let chosen: Vec<Monomial> = monomials
+                    .iter()
+                    .copied()
+                    .filter(|_| next() & 1 == 1)
+                    .collect();
+                let mut p = BooleanPolynomial::from_monomials(&chosen);
+                if p.evaluate_bits(solution) == 1 {
+                    p.toggle(Monomial::one());
+                }
+                p
+            })
+            .collect()
+    }
+
+    fn brute_force(polys: &[BooleanPolynomial], nvars: usize) -> Vec<Vec<u8>> {
+        let mut solutions = (0..1u64 << nvars)
+            .filter(|&point| polys.iter().all(|p| p.evaluate_bits(point) == 0))
+            .map(|point| (0..nvars).map(|v| ((point >> v) & 1) as u8).collect())
+            .collect::<Vec<_>>();
+        solutions.sort();
+        solutions
+    }
+
+    #[test]
+    fn test_macaulay_matrix() {
+        let polys = planted_system(4, 3, 0b1010, 3);
+        let (m, columns) = macaulay_matrix::<u16>(&polys, 4, 3, MonomialOrder::DegRevLex);
+
+        assert_eq!(m.nrows(), 3 * 5);
+        assert!(columns.len() <= 15);
+        for w in columns.windows(2) {
+            assert_eq!(
+                MonomialOrder::DegRevLex.compare(w[0], w[1]),
+                core::cmp::Ordering::Greater
+            );
+        }
+        // The first row is the first polynomial itself.
+        for (j, &c) in columns.iter().enumerate() {
+            let bit = (m.row(0) >> (columns.len() - 1 - j)) & 1;
+            assert_eq!(bit == 1, polys[0].contains(&c));
+        }
+    }
+
+    #[test]
+    fn test_xl_and_linearisation_find_all_solutions() {
+        for seed in 1..10 {
+            let polys = planted_system(6, 8, 0b101101 ^ seed, seed);
+            let expected = brute_force(&polys, 6);
+            assert!(expected.contains(
+                &(0..6)
+                    .map(|v| (((0b101101 ^ seed) >> v) & 1) as u8)
+                    .collect()
+            ));
+
+            assert_eq!(linearisation(&polys, 6), expected);
+            assert_eq!(xl(&polys, 6, 2), expected);
+            assert_eq!(xl(&polys, 6, 4), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "below the degree 2 of an equation")]
+    fn test_xl_rejects_degree_below_system_degree() {
+        let polys = planted_system(6, 8, 0b101101, 1);
+        xl(&polys, 6, 1);
+    }
+
+    #[test]
+    fn test_xl_exposes_linear_equations() {
+        let polys = planted_system(6, 6, 0b011001, 11);
+        let rows = echelonise(&macaulay_rows(&polys, 6, 4), MonomialOrder::DegRevLex);
+        let linear = rows.iter().filter(|r| r.degree() == Some(1)).count();
+        assert!(linear > 0);
+        assert_eq!(xl(&polys, 6, 4), brute_force(&polys, 6));
+    }
+
+    #[test]
+    fn test_xor_sat() {
+        let x = BooleanPolynomial::variable;
+        let one = BooleanPolynomial::one();
+
+        let equations = [
+            &(&x(0) + &x(2)) + &one,
+            &x(1) + &x(3),
+            &(&x(0) + &x(1)) + &x(3),
+        ];
+        let (particular, basis) = xor_sat(&equations, 4).unwrap();
+        // The last two equations add up to x0 = 0, so x2 = 1 and x1 = x3.
+        assert_eq!(particular, vec![0, 0, 1, 0]);
+        assert_eq!(basis, vec![vec![0, 1, 0, 1]]);
+        for e in &equations {
+            assert_eq!(e.evaluate(&particular), 0);
+            let mut other = particular.clone();
+            for (o, b) in other.iter_mut().zip(&basis[0]) {
+                *o ^= b;
+            }
+            assert_eq!(e.evaluate(&other), 0);
+        }
+
+        let inconsistent = [x(0), &x(0) + &one];
+        assert_eq!(xor_sat(&inconsistent, 1), None);
+        assert!(linearisation(&inconsistent, 1).is_empty());
+    }
+}